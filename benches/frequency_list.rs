@@ -0,0 +1,97 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use sentence_base::frequency_list::{FrequencyList, JpFrequencyList};
+use serde_json::Value;
+use std::collections::HashMap;
+
+const FREQUENCY_LIST_JSON: &str = include_str!("../frequency_lists/jp.json");
+
+const LOOKUPS: [(&str, &str); 6] = [
+    ("これ", "コレ"),
+    ("は", "ハ"),
+    ("ペン", "ペン"),
+    ("です", "デス"),
+    ("魑魅魍魎", "チミモウリョウ"),
+    ("ぽぷぴ", "ポプピ"),
+];
+
+// The loader that parsed the embedded JSON on every start, kept here as the baseline.
+struct LegacyFrequencyList {
+    lowest_frequency: usize,
+    frequency_hash_map: HashMap<(String, String), usize>,
+}
+
+impl LegacyFrequencyList {
+    fn new() -> Self {
+        let mut frequency_hash_map: HashMap<(String, String), usize> = HashMap::new();
+        let frequency_list_json: Value = serde_json::from_str(FREQUENCY_LIST_JSON).unwrap();
+        let words = frequency_list_json.as_array().unwrap();
+
+        for (index, word_value) in words.iter().enumerate() {
+            let word = word_value.as_array().unwrap();
+            let dictionary_form = word[0].as_str().unwrap();
+            let reading = word[1].as_str().unwrap();
+
+            frequency_hash_map.insert((dictionary_form.to_string(), reading.to_string()), index);
+        }
+
+        LegacyFrequencyList {
+            lowest_frequency: frequency_hash_map.len() + 1,
+            frequency_hash_map,
+        }
+    }
+
+    fn get_frequency(&self, word: &str, reading: &str) -> usize {
+        *self
+            .frequency_hash_map
+            .get(&(word.to_string(), reading.to_string()))
+            .unwrap_or(&self.lowest_frequency)
+    }
+}
+
+fn startup(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("frequency_list_startup");
+    group.sample_size(10);
+    group.bench_function("legacy_json", |bencher| {
+        bencher.iter(|| black_box(LegacyFrequencyList::new()))
+    });
+    group.bench_function("runtime_json", |bencher| {
+        bencher.iter(|| black_box(JpFrequencyList::from_json(FREQUENCY_LIST_JSON).unwrap()))
+    });
+    group.bench_function("bundled", |bencher| {
+        bencher.iter(|| black_box(JpFrequencyList::bundled()))
+    });
+    group.finish();
+}
+
+fn lookup(criterion: &mut Criterion) {
+    let legacy_frequency_list = LegacyFrequencyList::new();
+    let runtime_frequency_list = JpFrequencyList::from_json(FREQUENCY_LIST_JSON).unwrap();
+    let bundled_frequency_list = JpFrequencyList::bundled();
+
+    let mut group = criterion.benchmark_group("frequency_list_lookup");
+    group.bench_function("legacy_json", |bencher| {
+        bencher.iter(|| {
+            for (word, reading) in LOOKUPS {
+                black_box(legacy_frequency_list.get_frequency(word, reading));
+            }
+        })
+    });
+    group.bench_function("runtime_json", |bencher| {
+        bencher.iter(|| {
+            for (word, reading) in LOOKUPS {
+                black_box(runtime_frequency_list.get_frequency(word, reading));
+            }
+        })
+    });
+    group.bench_function("bundled", |bencher| {
+        bencher.iter(|| {
+            for (word, reading) in LOOKUPS {
+                black_box(bundled_frequency_list.get_frequency(word, reading));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, startup, lookup);
+criterion_main!(benches);
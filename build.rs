@@ -0,0 +1,77 @@
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+// Only part of the module is needed to index the list.
+#[allow(dead_code)]
+#[path = "src/frequency_list/normalization.rs"]
+mod normalization;
+
+use normalization::{normalize_reading, normalize_width};
+
+const FREQUENCY_LIST_PATH: &str = "frequency_lists/jp.json";
+const NORMALIZATION_PATH: &str = "src/frequency_list/normalization.rs";
+
+// Compiles the bundled frequency list into a perfect hash map from width-normalized dictionary
+// form to its entries, so it does not have to be parsed on every start. Entries are ranked like the
+// runtime loader: by their order without duplicates, which keep their first rank.
+fn main() {
+    println!("cargo:rerun-if-changed={}", FREQUENCY_LIST_PATH);
+    println!("cargo:rerun-if-changed={}", NORMALIZATION_PATH);
+
+    let frequency_list_json =
+        fs::read_to_string(FREQUENCY_LIST_PATH).expect("bundled frequency list should exist");
+    let frequency_list: Value =
+        serde_json::from_str(&frequency_list_json).expect("bundled frequency list should parse");
+
+    let mut frequencies: BTreeMap<(&str, &str), usize> = BTreeMap::new();
+    for word_value in frequency_list
+        .as_array()
+        .expect("bundled frequency list should be an array")
+    {
+        let word = word_value.as_array().expect("entry should be an array");
+        let dictionary_form = word[0]
+            .as_str()
+            .expect("dictionary form should be a string");
+        let reading = word[1].as_str().expect("reading should be a string");
+
+        let rank = frequencies.len();
+        frequencies
+            .entry((dictionary_form, reading))
+            .or_insert(rank);
+    }
+
+    let mut entries: BTreeMap<String, String> = BTreeMap::new();
+    for ((dictionary_form, reading), rank) in &frequencies {
+        write!(
+            entries
+                .entry(normalize_width(dictionary_form).into_owned())
+                .or_default(),
+            "({:?}, {:?}, {:?}, {}),",
+            dictionary_form,
+            reading,
+            normalize_reading(reading),
+            rank
+        )
+        .unwrap();
+    }
+
+    let mut frequency_table = phf_codegen::Map::new();
+    for (dictionary_form, entries) in &entries {
+        frequency_table.entry(dictionary_form.as_str(), &format!("&[{}]", entries));
+    }
+
+    let code = format!(
+        "pub const BUNDLED_WORD_COUNT: usize = {};\n\n\
+         pub static BUNDLED_FREQUENCY_TABLE: phf::Map<&'static str, &'static [(&'static str, &'static str, &'static str, u32)]> = {};\n",
+        frequencies.len(),
+        frequency_table.build()
+    );
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR should be set");
+    fs::write(Path::new(&out_dir).join("frequency_table.rs"), code)
+        .expect("frequency table should be written");
+}
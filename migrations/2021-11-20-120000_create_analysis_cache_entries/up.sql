@@ -0,0 +1,8 @@
+-- Your SQL goes here
+CREATE TABLE analysis_cache_entries (
+  configuration_key TEXT NOT NULL,
+  sentence TEXT NOT NULL,
+  morphemes TEXT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (configuration_key, sentence)
+);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE custom_frequency_list_entries;
+DROP TABLE custom_frequency_lists;
@@ -0,0 +1,23 @@
+-- Your SQL goes here
+CREATE TABLE custom_frequency_lists (
+  user_id INT PRIMARY KEY,
+  word_count INT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  CONSTRAINT fk_custom_frequency_lists_user_id
+    FOREIGN KEY (user_id)
+    REFERENCES users(id)
+);
+
+CREATE TABLE custom_frequency_list_entries (
+  id SERIAL PRIMARY KEY,
+  user_id INT NOT NULL,
+  normalized_form TEXT NOT NULL,
+  dictionary_form TEXT NOT NULL,
+  reading TEXT,
+  frequency INT NOT NULL,
+  CONSTRAINT fk_custom_frequency_list_entries_user_id
+    FOREIGN KEY (user_id)
+    REFERENCES custom_frequency_lists(user_id)
+);
+CREATE INDEX idx_custom_frequency_list_entries_user_id_normalized_form
+  ON custom_frequency_list_entries(user_id, normalized_form);
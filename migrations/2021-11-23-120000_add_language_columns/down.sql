@@ -0,0 +1,34 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX idx_custom_frequency_list_entries_user_id_language_normalized_form;
+ALTER TABLE custom_frequency_list_entries
+  DROP CONSTRAINT fk_custom_frequency_list_entries_user_id_language;
+DELETE FROM custom_frequency_list_entries WHERE language <> 'ja';
+ALTER TABLE custom_frequency_list_entries
+  DROP COLUMN language;
+DELETE FROM custom_frequency_lists WHERE language <> 'ja';
+ALTER TABLE custom_frequency_lists
+  DROP CONSTRAINT custom_frequency_lists_pkey;
+ALTER TABLE custom_frequency_lists
+  DROP COLUMN language;
+ALTER TABLE custom_frequency_lists
+  ADD PRIMARY KEY (user_id);
+ALTER TABLE custom_frequency_list_entries
+  ADD CONSTRAINT fk_custom_frequency_list_entries_user_id
+  FOREIGN KEY (user_id)
+  REFERENCES custom_frequency_lists(user_id);
+CREATE INDEX idx_custom_frequency_list_entries_user_id_normalized_form
+  ON custom_frequency_list_entries(user_id, normalized_form);
+
+ALTER TABLE sentences
+  DROP COLUMN language;
+
+ALTER TABLE words
+  DROP CONSTRAINT words_user_id_language_dictionary_form_reading_key;
+ALTER TABLE words
+  DROP COLUMN language;
+ALTER TABLE words
+  ADD CONSTRAINT words_user_id_dictionary_form_reading_key
+  UNIQUE (user_id, dictionary_form, reading);
+
+ALTER TABLE users
+  DROP COLUMN language;
@@ -0,0 +1,32 @@
+-- Your SQL goes here
+ALTER TABLE users
+  ADD COLUMN language TEXT NOT NULL DEFAULT 'ja';
+
+ALTER TABLE words
+  ADD COLUMN language TEXT NOT NULL DEFAULT 'ja';
+ALTER TABLE words
+  DROP CONSTRAINT words_user_id_dictionary_form_reading_key;
+ALTER TABLE words
+  ADD CONSTRAINT words_user_id_language_dictionary_form_reading_key
+  UNIQUE (user_id, language, dictionary_form, reading);
+
+ALTER TABLE sentences
+  ADD COLUMN language TEXT NOT NULL DEFAULT 'ja';
+
+ALTER TABLE custom_frequency_list_entries
+  DROP CONSTRAINT fk_custom_frequency_list_entries_user_id;
+ALTER TABLE custom_frequency_lists
+  DROP CONSTRAINT custom_frequency_lists_pkey;
+ALTER TABLE custom_frequency_lists
+  ADD COLUMN language TEXT NOT NULL DEFAULT 'ja';
+ALTER TABLE custom_frequency_lists
+  ADD PRIMARY KEY (user_id, language);
+ALTER TABLE custom_frequency_list_entries
+  ADD COLUMN language TEXT NOT NULL DEFAULT 'ja';
+ALTER TABLE custom_frequency_list_entries
+  ADD CONSTRAINT fk_custom_frequency_list_entries_user_id_language
+  FOREIGN KEY (user_id, language)
+  REFERENCES custom_frequency_lists(user_id, language);
+DROP INDEX idx_custom_frequency_list_entries_user_id_normalized_form;
+CREATE INDEX idx_custom_frequency_list_entries_user_id_language_normalized_form
+  ON custom_frequency_list_entries(user_id, language, normalized_form);
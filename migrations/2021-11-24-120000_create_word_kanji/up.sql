@@ -0,0 +1,21 @@
+-- Your SQL goes here
+CREATE TABLE word_kanji (
+  word_id INT NOT NULL,
+  user_id INT NOT NULL,
+  kanji TEXT NOT NULL,
+  PRIMARY KEY (word_id, kanji),
+  CONSTRAINT fk_word_kanji_word_id
+    FOREIGN KEY (word_id)
+    REFERENCES words(id),
+  CONSTRAINT fk_word_kanji_user_id
+    FOREIGN KEY (user_id)
+    REFERENCES users(id)
+);
+CREATE INDEX idx_word_kanji_user_id_kanji ON word_kanji(user_id, kanji);
+
+-- Same characters as extract_kanji: the CJK ideograph blocks only. The iteration mark 々 and the
+-- counters 〆, ヵ and ヶ repeat or abbreviate other characters and are not tracked as kanji.
+INSERT INTO word_kanji (word_id, user_id, kanji)
+SELECT DISTINCT words.id, words.user_id, characters.kanji
+FROM words, regexp_split_to_table(words.dictionary_form, '') AS characters(kanji)
+WHERE characters.kanji ~ '^[一-鿿㐀-䶿豈-﫿]$';
@@ -0,0 +1,13 @@
+-- Your SQL goes here
+CREATE TABLE invites (
+  id SERIAL PRIMARY KEY,
+  code TEXT NOT NULL UNIQUE,
+  created_by INT NOT NULL,
+  remaining_uses INT NOT NULL,
+  expires_at TIMESTAMPTZ,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  CONSTRAINT fk_invites_created_by
+    FOREIGN KEY (created_by)
+    REFERENCES users(id)
+);
+CREATE INDEX idx_invites_created_by ON invites(created_by);
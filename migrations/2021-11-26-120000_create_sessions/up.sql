@@ -0,0 +1,14 @@
+-- Your SQL goes here
+CREATE TABLE sessions (
+  id SERIAL PRIMARY KEY,
+  user_id INT NOT NULL,
+  device_name TEXT,
+  user_agent TEXT,
+  last_ip TEXT,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  last_used_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  CONSTRAINT fk_sessions_user_id
+    FOREIGN KEY (user_id)
+    REFERENCES users(id)
+);
+CREATE INDEX idx_sessions_user_id ON sessions(user_id);
@@ -0,0 +1,15 @@
+-- Your SQL goes here
+CREATE TABLE personal_access_tokens (
+  id SERIAL PRIMARY KEY,
+  user_id INT NOT NULL,
+  name TEXT NOT NULL,
+  token_hash TEXT NOT NULL UNIQUE,
+  scopes TEXT[] NOT NULL,
+  expires_at TIMESTAMPTZ,
+  last_used_at TIMESTAMPTZ,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  CONSTRAINT fk_personal_access_tokens_user_id
+    FOREIGN KEY (user_id)
+    REFERENCES users(id)
+);
+CREATE INDEX idx_personal_access_tokens_user_id ON personal_access_tokens(user_id);
@@ -0,0 +1,2 @@
+-- Your SQL goes here
+CREATE INDEX idx_analysis_cache_entries_created_at ON analysis_cache_entries(created_at);
@@ -0,0 +1,187 @@
+use crate::analyzer::Morpheme;
+use crate::helpers::{
+    get_analysis_cache_max_age, get_analysis_cache_persistent, get_analysis_cache_size,
+};
+use crate::models::analysis_cache_entry::AnalysisCacheEntry;
+use chrono::Duration;
+use diesel::pg::PgConnection;
+use diesel::result::Error;
+use lru::LruCache;
+use rocket::serde::Serialize;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+type AnalysisCacheKey = (String, String);
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CacheStatus {
+    Hit,
+    Miss,
+}
+
+impl CacheStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CacheStatus::Hit => "hit",
+            CacheStatus::Miss => "miss",
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct AnalysisCacheStatistics {
+    pub hits: u64,
+    pub misses: u64,
+    pub size: usize,
+    pub capacity: usize,
+    pub is_persistent: bool,
+}
+
+/// Analysis results keyed by analyzer configuration and sentence. The in-memory LRU is consulted
+/// first; when persistence is enabled, misses fall through to the `analysis_cache_entries` table
+/// so results survive restarts. A capacity of 0 disables the in-memory layer. Persisted entries
+/// older than the maximum age are pruned as new ones are saved; without one, the table keeps
+/// growing with every distinct sentence analyzed.
+pub struct AnalysisCache {
+    entries: Option<Mutex<LruCache<AnalysisCacheKey, Vec<Morpheme>>>>,
+    is_persistent: bool,
+    max_age: Option<Duration>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl AnalysisCache {
+    pub fn new(capacity: usize, is_persistent: bool, max_age: Option<Duration>) -> Self {
+        AnalysisCache {
+            entries: NonZeroUsize::new(capacity)
+                .map(|capacity| Mutex::new(LruCache::new(capacity))),
+            is_persistent,
+            max_age,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    fn get_from_memory(&self, configuration_key: &str, sentence: &str) -> Option<Vec<Morpheme>> {
+        let mut entries = self.entries.as_ref()?.lock().ok()?;
+
+        entries
+            .get(&(configuration_key.to_string(), sentence.to_string()))
+            .cloned()
+    }
+
+    fn insert_into_memory(&self, configuration_key: &str, sentence: &str, morphemes: &[Morpheme]) {
+        if let Some(mut entries) = self
+            .entries
+            .as_ref()
+            .and_then(|entries| entries.lock().ok())
+        {
+            entries.put(
+                (configuration_key.to_string(), sentence.to_string()),
+                morphemes.to_vec(),
+            );
+        }
+    }
+
+    /// Cached analyses of `sentences`, in order. Sentences missing from memory are looked up in
+    /// the database in one query.
+    pub fn get_all(
+        &self,
+        database_connection: &PgConnection,
+        configuration_key: &str,
+        sentences: &[String],
+    ) -> Result<Vec<Option<Vec<Morpheme>>>, Error> {
+        let mut analyses = sentences
+            .iter()
+            .map(|sentence| self.get_from_memory(configuration_key, sentence))
+            .collect::<Vec<Option<Vec<Morpheme>>>>();
+
+        let uncached_sentences = sentences
+            .iter()
+            .zip(&analyses)
+            .filter(|(_, morphemes)| morphemes.is_none())
+            .map(|(sentence, _)| sentence.as_str())
+            .collect::<Vec<&str>>();
+
+        if self.is_persistent && !uncached_sentences.is_empty() {
+            let mut stored_analyses = AnalysisCacheEntry::find_all_morphemes(
+                database_connection,
+                configuration_key,
+                &uncached_sentences,
+            )?;
+
+            for (sentence, morphemes) in sentences.iter().zip(analyses.iter_mut()) {
+                if morphemes.is_none() {
+                    *morphemes = stored_analyses.remove(sentence);
+
+                    if let Some(morphemes) = morphemes {
+                        self.insert_into_memory(configuration_key, sentence, morphemes);
+                    }
+                }
+            }
+        }
+
+        for morphemes in &analyses {
+            let counter = match morphemes {
+                Some(_) => &self.hits,
+                None => &self.misses,
+            };
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+
+        Ok(analyses)
+    }
+
+    pub fn insert_all(
+        &self,
+        database_connection: &PgConnection,
+        configuration_key: &str,
+        analyses: &[(&str, &[Morpheme])],
+    ) -> Result<(), Error> {
+        for (sentence, morphemes) in analyses {
+            self.insert_into_memory(configuration_key, sentence, morphemes);
+        }
+
+        if self.is_persistent {
+            AnalysisCacheEntry::save_all(
+                database_connection,
+                configuration_key,
+                analyses,
+                self.max_age,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_statistics(&self) -> AnalysisCacheStatistics {
+        let (size, capacity) = self
+            .entries
+            .as_ref()
+            .and_then(|entries| entries.lock().ok())
+            .map(|entries| (entries.len(), entries.cap().get()))
+            .unwrap_or((0, 0));
+
+        AnalysisCacheStatistics {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            size,
+            capacity,
+            is_persistent: self.is_persistent,
+        }
+    }
+}
+
+pub fn init_analysis_cache() -> AnalysisCache {
+    let max_age = match get_analysis_cache_max_age() {
+        0 => None,
+        max_age => Some(Duration::seconds(max_age as i64)),
+    };
+
+    AnalysisCache::new(
+        get_analysis_cache_size(),
+        get_analysis_cache_persistent(),
+        max_age,
+    )
+}
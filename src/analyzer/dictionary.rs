@@ -0,0 +1,201 @@
+use crate::analyzer::part_of_speech::PartOfSpeech;
+use crate::helpers::get_dictionary_flavor;
+
+pub trait FeatureMapping: Send + Sync {
+    fn part_of_speech(&self, features: &[String]) -> PartOfSpeech;
+    fn part_of_speech_subcategories(&self, features: &[String]) -> Vec<String>;
+    fn conjugation_type(&self, features: &[String]) -> Option<String>;
+    fn conjugation_form(&self, features: &[String]) -> Option<String>;
+    fn dictionary_form(&self, features: &[String]) -> Option<String>;
+    fn surface_reading(&self, features: &[String]) -> Option<String>;
+
+    /// Reading of the dictionary form when the dictionary provides one directly. Flavors that
+    /// return `None` have the dictionary form re-analyzed to look its reading up.
+    fn dictionary_form_reading(&self, _features: &[String]) -> Option<String> {
+        None
+    }
+}
+
+pub fn init_feature_mapping() -> Box<dyn FeatureMapping> {
+    match get_dictionary_flavor().as_str() {
+        "ipadic" => Box::new(IpadicFeatureMapping),
+        "neologd" => Box::new(NeologdFeatureMapping),
+        "unidic" => Box::new(UnidicFeatureMapping),
+        flavor => panic!("dictionary flavor \"{}\" is not supported", flavor),
+    }
+}
+
+fn get_feature(features: &[String], index: usize) -> Option<&str> {
+    features
+        .get(index)
+        .map(|feature| feature.as_str())
+        .filter(|feature| !feature.is_empty() && *feature != "*")
+}
+
+fn get_owned_feature(features: &[String], index: usize) -> Option<String> {
+    get_feature(features, index).map(|feature| feature.to_string())
+}
+
+fn get_subcategories(features: &[String], indices: &[usize]) -> Vec<String> {
+    indices
+        .iter()
+        .filter_map(|index| get_owned_feature(features, *index))
+        .collect()
+}
+
+const IPADIC_PART_OF_SPEECH_INDEX: usize = 0;
+const IPADIC_PART_OF_SPEECH_SUBCATEGORY_INDICES: [usize; 3] = [1, 2, 3];
+const IPADIC_CONJUGATION_TYPE_INDEX: usize = 4;
+const IPADIC_CONJUGATION_FORM_INDEX: usize = 5;
+const IPADIC_DICTIONARY_FORM_INDEX: usize = 6;
+const IPADIC_READING_INDEX: usize = 7;
+
+fn ipadic_part_of_speech(feature: &str) -> PartOfSpeech {
+    match feature {
+        "名詞" => PartOfSpeech::Noun,
+        "動詞" => PartOfSpeech::Verb,
+        "形容詞" => PartOfSpeech::Adjective,
+        "副詞" => PartOfSpeech::Adverb,
+        "連体詞" => PartOfSpeech::Adnominal,
+        "接続詞" => PartOfSpeech::Conjunction,
+        "感動詞" => PartOfSpeech::Interjection,
+        "助詞" => PartOfSpeech::Particle,
+        "助動詞" => PartOfSpeech::AuxiliaryVerb,
+        "接頭詞" => PartOfSpeech::Prefix,
+        "記号" => PartOfSpeech::Symbol,
+        "フィラー" => PartOfSpeech::Filler,
+        "その他" => PartOfSpeech::Other,
+        _ => PartOfSpeech::Unknown,
+    }
+}
+
+/// Feature layout of mecab-ipadic: `品詞,品詞細分類1,品詞細分類2,品詞細分類3,活用型,活用形,原形,読み,発音`.
+/// Unknown words only carry the first seven columns.
+pub struct IpadicFeatureMapping;
+
+impl FeatureMapping for IpadicFeatureMapping {
+    fn part_of_speech(&self, features: &[String]) -> PartOfSpeech {
+        get_feature(features, IPADIC_PART_OF_SPEECH_INDEX)
+            .map(ipadic_part_of_speech)
+            .unwrap_or(PartOfSpeech::Unknown)
+    }
+
+    fn part_of_speech_subcategories(&self, features: &[String]) -> Vec<String> {
+        get_subcategories(features, &IPADIC_PART_OF_SPEECH_SUBCATEGORY_INDICES)
+    }
+
+    fn conjugation_type(&self, features: &[String]) -> Option<String> {
+        get_owned_feature(features, IPADIC_CONJUGATION_TYPE_INDEX)
+    }
+
+    fn conjugation_form(&self, features: &[String]) -> Option<String> {
+        get_owned_feature(features, IPADIC_CONJUGATION_FORM_INDEX)
+    }
+
+    fn dictionary_form(&self, features: &[String]) -> Option<String> {
+        get_owned_feature(features, IPADIC_DICTIONARY_FORM_INDEX)
+    }
+
+    fn surface_reading(&self, features: &[String]) -> Option<String> {
+        get_owned_feature(features, IPADIC_READING_INDEX)
+    }
+}
+
+/// mecab-ipadic-NEologd extends ipadic with new words and proper nouns using the same columns.
+pub struct NeologdFeatureMapping;
+
+impl FeatureMapping for NeologdFeatureMapping {
+    fn part_of_speech(&self, features: &[String]) -> PartOfSpeech {
+        IpadicFeatureMapping.part_of_speech(features)
+    }
+
+    fn part_of_speech_subcategories(&self, features: &[String]) -> Vec<String> {
+        IpadicFeatureMapping.part_of_speech_subcategories(features)
+    }
+
+    fn conjugation_type(&self, features: &[String]) -> Option<String> {
+        IpadicFeatureMapping.conjugation_type(features)
+    }
+
+    fn conjugation_form(&self, features: &[String]) -> Option<String> {
+        IpadicFeatureMapping.conjugation_form(features)
+    }
+
+    fn dictionary_form(&self, features: &[String]) -> Option<String> {
+        IpadicFeatureMapping.dictionary_form(features)
+    }
+
+    fn surface_reading(&self, features: &[String]) -> Option<String> {
+        IpadicFeatureMapping.surface_reading(features)
+    }
+}
+
+const UNIDIC_PART_OF_SPEECH_INDEX: usize = 0;
+const UNIDIC_PART_OF_SPEECH_SUBCATEGORY_INDICES: [usize; 3] = [1, 2, 3];
+const UNIDIC_CONJUGATION_TYPE_INDEX: usize = 4;
+const UNIDIC_CONJUGATION_FORM_INDEX: usize = 5;
+const UNIDIC_LEMMA_READING_INDEX: usize = 6;
+const UNIDIC_LEMMA_INDEX: usize = 7;
+const UNIDIC_PRONUNCIATION_INDEX: usize = 9;
+const UNIDIC_KANA_INDEX: usize = 20;
+
+fn unidic_part_of_speech(feature: &str) -> PartOfSpeech {
+    match feature {
+        "名詞" => PartOfSpeech::Noun,
+        "代名詞" => PartOfSpeech::Pronoun,
+        "形状詞" => PartOfSpeech::AdjectivalNoun,
+        "動詞" => PartOfSpeech::Verb,
+        "形容詞" => PartOfSpeech::Adjective,
+        "副詞" => PartOfSpeech::Adverb,
+        "連体詞" => PartOfSpeech::Adnominal,
+        "接続詞" => PartOfSpeech::Conjunction,
+        "感動詞" => PartOfSpeech::Interjection,
+        "助詞" => PartOfSpeech::Particle,
+        "助動詞" => PartOfSpeech::AuxiliaryVerb,
+        "接頭辞" => PartOfSpeech::Prefix,
+        "接尾辞" => PartOfSpeech::Suffix,
+        "記号" | "補助記号" | "空白" => PartOfSpeech::Symbol,
+        _ => PartOfSpeech::Unknown,
+    }
+}
+
+/// Feature layout of UniDic for MeCab: `pos1,pos2,pos3,pos4,cType,cForm,lForm,lemma,orth,pron,...`,
+/// where recent releases also carry the surface kana at index 20. Lemmas of loanwords are suffixed
+/// with their origin (`ペン-pen`), which is stripped.
+pub struct UnidicFeatureMapping;
+
+impl FeatureMapping for UnidicFeatureMapping {
+    fn part_of_speech(&self, features: &[String]) -> PartOfSpeech {
+        get_feature(features, UNIDIC_PART_OF_SPEECH_INDEX)
+            .map(unidic_part_of_speech)
+            .unwrap_or(PartOfSpeech::Unknown)
+    }
+
+    fn part_of_speech_subcategories(&self, features: &[String]) -> Vec<String> {
+        get_subcategories(features, &UNIDIC_PART_OF_SPEECH_SUBCATEGORY_INDICES)
+    }
+
+    fn conjugation_type(&self, features: &[String]) -> Option<String> {
+        get_owned_feature(features, UNIDIC_CONJUGATION_TYPE_INDEX)
+    }
+
+    fn conjugation_form(&self, features: &[String]) -> Option<String> {
+        get_owned_feature(features, UNIDIC_CONJUGATION_FORM_INDEX)
+    }
+
+    fn dictionary_form(&self, features: &[String]) -> Option<String> {
+        get_feature(features, UNIDIC_LEMMA_INDEX).map(|lemma| match lemma.split_once('-') {
+            Some((lemma, _)) if !lemma.is_empty() => lemma.to_string(),
+            _ => lemma.to_string(),
+        })
+    }
+
+    fn surface_reading(&self, features: &[String]) -> Option<String> {
+        get_owned_feature(features, UNIDIC_KANA_INDEX)
+            .or_else(|| get_owned_feature(features, UNIDIC_PRONUNCIATION_INDEX))
+    }
+
+    fn dictionary_form_reading(&self, features: &[String]) -> Option<String> {
+        get_owned_feature(features, UNIDIC_LEMMA_READING_INDEX)
+    }
+}
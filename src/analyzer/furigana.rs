@@ -0,0 +1,165 @@
+use crate::analyzer::Morpheme;
+use rocket::serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct FuriganaSegment {
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reading: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct SentenceFurigana {
+    pub anki: String,
+    pub html: String,
+}
+
+pub fn is_kanji(character: char) -> bool {
+    matches!(
+        character,
+        '\u{4E00}'..='\u{9FFF}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '々'
+            | '〆'
+            | 'ヵ'
+            | 'ヶ'
+    )
+}
+
+pub fn katakana_to_hiragana(text: &str) -> String {
+    text.chars()
+        .map(|character| match character {
+            'ァ'..='ヶ' => char::from_u32(character as u32 - 0x60).unwrap_or(character),
+            _ => character,
+        })
+        .collect()
+}
+
+fn split_into_runs(surface: &str) -> Vec<(bool, String)> {
+    let mut runs: Vec<(bool, String)> = vec![];
+
+    for character in surface.chars() {
+        let character_is_kanji = is_kanji(character);
+
+        match runs.last_mut() {
+            Some((run_is_kanji, run)) if *run_is_kanji == character_is_kanji => run.push(character),
+            _ => runs.push((character_is_kanji, character.to_string())),
+        }
+    }
+
+    runs
+}
+
+fn match_runs(runs: &[(bool, String)], reading: &[char]) -> Option<Vec<String>> {
+    let ((is_kanji_run, run), remaining_runs) = match runs.split_first() {
+        None => {
+            return if reading.is_empty() {
+                Some(vec![])
+            } else {
+                None
+            }
+        }
+        Some(split) => split,
+    };
+
+    if !is_kanji_run {
+        let kana = katakana_to_hiragana(run).chars().collect::<Vec<char>>();
+
+        if !reading.starts_with(&kana) {
+            return None;
+        }
+
+        return match_runs(remaining_runs, &reading[kana.len()..]);
+    }
+
+    let maximum_length = if remaining_runs.is_empty() {
+        reading.len()
+    } else {
+        reading.len().saturating_sub(1)
+    };
+
+    (1..=maximum_length).find_map(|length| {
+        let mut readings = match_runs(remaining_runs, &reading[length..])?;
+        readings.insert(0, reading[..length].iter().collect());
+        Some(readings)
+    })
+}
+
+pub fn segment_furigana(surface: &str, reading: &str) -> Vec<FuriganaSegment> {
+    let runs = split_into_runs(surface);
+
+    if runs.iter().all(|(is_kanji_run, _)| !is_kanji_run) {
+        return vec![FuriganaSegment {
+            text: surface.to_string(),
+            reading: None,
+        }];
+    }
+
+    let reading = katakana_to_hiragana(reading).chars().collect::<Vec<char>>();
+
+    match match_runs(&runs, &reading) {
+        None => vec![FuriganaSegment {
+            text: surface.to_string(),
+            reading: Some(reading.iter().collect()),
+        }],
+        Some(kanji_readings) => {
+            let mut kanji_readings = kanji_readings.into_iter();
+
+            runs.into_iter()
+                .map(|(is_kanji_run, text)| FuriganaSegment {
+                    text,
+                    reading: if is_kanji_run {
+                        kanji_readings.next()
+                    } else {
+                        None
+                    },
+                })
+                .collect()
+        }
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub fn render_sentence_furigana(morphemes: &[Morpheme]) -> SentenceFurigana {
+    let mut anki = String::new();
+    let mut html = String::new();
+
+    let segments = morphemes
+        .iter()
+        .flat_map(|morpheme| match &morpheme.furigana {
+            Some(segments) => segments.clone(),
+            None => vec![FuriganaSegment {
+                text: morpheme.morpheme.clone(),
+                reading: None,
+            }],
+        });
+
+    for segment in segments {
+        match segment.reading {
+            None => {
+                anki.push_str(&segment.text);
+                html.push_str(&escape_html(&segment.text));
+            }
+            Some(reading) => {
+                if !anki.is_empty() {
+                    anki.push(' ');
+                }
+                anki.push_str(&format!("{}[{}]", segment.text, reading));
+                html.push_str(&format!(
+                    "<ruby>{}<rt>{}</rt></ruby>",
+                    escape_html(&segment.text),
+                    escape_html(&reading)
+                ));
+            }
+        }
+    }
+
+    SentenceFurigana { anki, html }
+}
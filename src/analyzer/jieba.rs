@@ -0,0 +1,157 @@
+use crate::analyzer::dictionary::FeatureMapping;
+use crate::analyzer::part_of_speech::PartOfSpeech;
+use crate::analyzer::pinyin::PinyinDictionary;
+use crate::analyzer::{Analyzer, Token};
+use crate::helpers::get_chinese_dictionary_path;
+use jieba_rs::Jieba;
+use std::path::Path;
+
+const JIEBA_TAG_INDEX: usize = 0;
+const JIEBA_PINYIN_INDEX: usize = 1;
+
+/// Tag jieba gives to punctuation, which it otherwise reports as unknown words.
+const PUNCTUATION_TAG: &str = "w";
+
+fn jieba_part_of_speech(tag: &str) -> PartOfSpeech {
+    if tag == "eng" {
+        return PartOfSpeech::Other;
+    }
+
+    match tag.chars().next() {
+        Some('n' | 't' | 's' | 'f') => PartOfSpeech::Noun,
+        Some('r') => PartOfSpeech::Pronoun,
+        Some('v') => PartOfSpeech::Verb,
+        Some('a' | 'b' | 'z') => PartOfSpeech::Adjective,
+        Some('d') => PartOfSpeech::Adverb,
+        Some('c') => PartOfSpeech::Conjunction,
+        Some('e' | 'o') => PartOfSpeech::Interjection,
+        Some('p' | 'u' | 'y') => PartOfSpeech::Particle,
+        Some('h') => PartOfSpeech::Prefix,
+        Some('k') => PartOfSpeech::Suffix,
+        Some('w') => PartOfSpeech::Symbol,
+        Some('m' | 'q' | 'i' | 'l' | 'j' | 'g') => PartOfSpeech::Other,
+        _ => PartOfSpeech::Unknown,
+    }
+}
+
+/// Features produced by [`JiebaAnalyzer`]: `tag,pinyin`, where the tag follows the ICTCLAS tag set
+/// used by jieba and the pinyin is `*` for words missing from the dictionary. Chinese words do not
+/// conjugate, so the dictionary form of a word is its surface.
+pub struct JiebaFeatureMapping;
+
+impl FeatureMapping for JiebaFeatureMapping {
+    fn part_of_speech(&self, features: &[String]) -> PartOfSpeech {
+        features
+            .get(JIEBA_TAG_INDEX)
+            .map(|tag| jieba_part_of_speech(tag))
+            .unwrap_or(PartOfSpeech::Unknown)
+    }
+
+    fn part_of_speech_subcategories(&self, features: &[String]) -> Vec<String> {
+        features.get(JIEBA_TAG_INDEX).cloned().into_iter().collect()
+    }
+
+    fn conjugation_type(&self, _features: &[String]) -> Option<String> {
+        None
+    }
+
+    fn conjugation_form(&self, _features: &[String]) -> Option<String> {
+        None
+    }
+
+    fn dictionary_form(&self, _features: &[String]) -> Option<String> {
+        None
+    }
+
+    fn surface_reading(&self, features: &[String]) -> Option<String> {
+        features
+            .get(JIEBA_PINYIN_INDEX)
+            .filter(|pinyin| !pinyin.is_empty() && *pinyin != "*")
+            .cloned()
+    }
+
+    fn dictionary_form_reading(&self, features: &[String]) -> Option<String> {
+        self.surface_reading(features)
+    }
+}
+
+/// Segments Chinese text with jieba's bundled dictionary and reads words as pinyin from an
+/// optional CC-CEDICT dictionary.
+pub struct JiebaAnalyzer {
+    jieba: Jieba,
+    pinyin_dictionary: PinyinDictionary,
+    configuration_key: String,
+}
+
+impl JiebaAnalyzer {
+    pub fn new() -> Self {
+        let dictionary_path = get_chinese_dictionary_path();
+        let pinyin_dictionary = match &dictionary_path {
+            Some(dictionary_path) => PinyinDictionary::load(Path::new(dictionary_path))
+                .expect("chinese dictionary should load"),
+            None => PinyinDictionary::empty(),
+        };
+
+        JiebaAnalyzer {
+            configuration_key: format!("jieba:{}", dictionary_path.unwrap_or_default()),
+            ..Self::with_pinyin_dictionary(pinyin_dictionary)
+        }
+    }
+
+    pub fn with_pinyin_dictionary(pinyin_dictionary: PinyinDictionary) -> Self {
+        JiebaAnalyzer {
+            jieba: Jieba::new(),
+            pinyin_dictionary,
+            configuration_key: "jieba:".to_string(),
+        }
+    }
+}
+
+impl Analyzer for JiebaAnalyzer {
+    fn tokenize(&self, text: &str) -> Vec<Token> {
+        self.jieba
+            .tag(text, true)
+            .into_iter()
+            .filter(|tag| !tag.word.trim().is_empty())
+            .map(|tag| {
+                // Every word is a slice of the text, so its offset is its distance from the start.
+                let byte_start = tag.word.as_ptr() as usize - text.as_ptr() as usize;
+                let is_punctuation = tag.tag == "x"
+                    && !tag
+                        .word
+                        .chars()
+                        .any(|character| character.is_alphanumeric());
+
+                Token {
+                    surface: tag.word.to_string(),
+                    byte_start,
+                    byte_end: byte_start + tag.word.len(),
+                    features: vec![
+                        if is_punctuation {
+                            PUNCTUATION_TAG.to_string()
+                        } else {
+                            tag.tag.to_string()
+                        },
+                        self.pinyin_dictionary
+                            .get_reading(tag.word)
+                            .unwrap_or_else(|| "*".to_string()),
+                    ],
+                }
+            })
+            .collect()
+    }
+
+    fn feature_mapping(&self) -> &dyn FeatureMapping {
+        &JiebaFeatureMapping
+    }
+
+    fn configuration_key(&self) -> String {
+        self.configuration_key.clone()
+    }
+}
+
+impl Default for JiebaAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,60 @@
+use crate::analyzer::dictionary::{init_feature_mapping, FeatureMapping};
+use crate::analyzer::{Analyzer, Token};
+use crate::helpers::{get_dictionary_flavor, get_lindera_dictionary};
+use lindera::dictionary::load_dictionary;
+use lindera::mode::Mode;
+use lindera::segmenter::Segmenter;
+use std::borrow::Cow;
+
+pub struct LinderaAnalyzer {
+    segmenter: Segmenter,
+    feature_mapping: Box<dyn FeatureMapping>,
+    configuration_key: String,
+}
+
+impl LinderaAnalyzer {
+    pub fn new() -> Self {
+        let dictionary_uri = get_lindera_dictionary();
+        let dictionary = load_dictionary(&dictionary_uri).expect("lindera dictionary should load");
+
+        LinderaAnalyzer {
+            segmenter: Segmenter::new(Mode::Normal, dictionary, None),
+            feature_mapping: init_feature_mapping(),
+            configuration_key: format!("lindera:{}:{}", get_dictionary_flavor(), dictionary_uri),
+        }
+    }
+}
+
+impl Analyzer for LinderaAnalyzer {
+    fn tokenize(&self, text: &str) -> Vec<Token> {
+        self.segmenter
+            .segment(Cow::Borrowed(text))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|mut token| Token {
+                surface: token.surface.to_string(),
+                byte_start: token.byte_start,
+                byte_end: token.byte_end,
+                features: token
+                    .details()
+                    .into_iter()
+                    .map(|feature| feature.to_string())
+                    .collect(),
+            })
+            .collect()
+    }
+
+    fn feature_mapping(&self) -> &dyn FeatureMapping {
+        self.feature_mapping.as_ref()
+    }
+
+    fn configuration_key(&self) -> String {
+        self.configuration_key.clone()
+    }
+}
+
+impl Default for LinderaAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,121 @@
+use crate::analyzer::dictionary::{init_feature_mapping, FeatureMapping};
+use crate::analyzer::{Analyzer, Token};
+use crate::helpers::{get_dictionary_flavor, get_mecab_dictionary_path, get_mecab_pool_size};
+use mecab::Tagger;
+use std::convert::Infallible;
+use std::ops::{Deref, DerefMut};
+
+pub struct PooledTagger(Tagger);
+
+// A MeCab tagger is not bound to the thread that created it, it just must not be used by two
+// threads at once, which the pool guarantees by handing out exclusive access.
+unsafe impl Send for PooledTagger {}
+
+impl Deref for PooledTagger {
+    type Target = Tagger;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for PooledTagger {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+pub struct TaggerManager {
+    arguments: String,
+}
+
+impl r2d2::ManageConnection for TaggerManager {
+    type Connection = PooledTagger;
+    type Error = Infallible;
+
+    fn connect(&self) -> Result<Self::Connection, Self::Error> {
+        Ok(PooledTagger(Tagger::new(self.arguments.as_str())))
+    }
+
+    fn is_valid(&self, _: &mut Self::Connection) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn has_broken(&self, _: &mut Self::Connection) -> bool {
+        false
+    }
+}
+
+pub struct MecabAnalyzer {
+    tagger_pool: r2d2::Pool<TaggerManager>,
+    feature_mapping: Box<dyn FeatureMapping>,
+    configuration_key: String,
+}
+
+impl MecabAnalyzer {
+    pub fn new() -> Self {
+        let dictionary_path = get_mecab_dictionary_path();
+        let arguments = match &dictionary_path {
+            Some(dictionary_path) => format!("-d {}", dictionary_path),
+            None => "".to_string(),
+        };
+        let configuration_key = format!(
+            "mecab:{}:{}",
+            get_dictionary_flavor(),
+            dictionary_path.unwrap_or_default()
+        );
+
+        let tagger_pool = r2d2::Pool::builder()
+            .max_size(get_mecab_pool_size())
+            .build(TaggerManager { arguments })
+            .expect("MeCab tagger pool");
+
+        MecabAnalyzer {
+            tagger_pool,
+            feature_mapping: init_feature_mapping(),
+            configuration_key,
+        }
+    }
+}
+
+impl Analyzer for MecabAnalyzer {
+    fn tokenize(&self, text: &str) -> Vec<Token> {
+        let mut tagger = self
+            .tagger_pool
+            .get()
+            .expect("MeCab tagger should be available");
+
+        tagger
+            .parse_to_node(text)
+            .iter_next()
+            .filter(|node| {
+                node.stat as i32 != mecab::MECAB_BOS_NODE
+                    && node.stat as i32 != mecab::MECAB_EOS_NODE
+            })
+            .map(|node| Token {
+                surface: node.surface[..node.length as usize].to_string(),
+                byte_start: text.len() - node.surface.len(),
+                byte_end: text.len() - node.surface.len() + node.length as usize,
+                features: node
+                    .feature
+                    .split(',')
+                    .map(|feature| feature.to_string())
+                    .collect(),
+            })
+            .collect()
+    }
+
+    fn feature_mapping(&self) -> &dyn FeatureMapping {
+        self.feature_mapping.as_ref()
+    }
+
+    fn configuration_key(&self) -> String {
+        self.configuration_key.clone()
+    }
+}
+
+impl Default for MecabAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,220 @@
+extern crate serde;
+
+pub mod cache;
+pub mod dictionary;
+pub mod furigana;
+#[cfg(feature = "jieba")]
+pub mod jieba;
+#[cfg(feature = "lindera")]
+pub mod lindera;
+#[cfg(feature = "mecab")]
+mod mecab;
+pub mod part_of_speech;
+pub mod pinyin;
+pub mod sentence_splitter;
+
+use crate::analyzer::dictionary::FeatureMapping;
+use crate::analyzer::furigana::{segment_furigana, FuriganaSegment};
+use crate::analyzer::part_of_speech::PartOfSpeech;
+use crate::helpers::get_analyzer_backend;
+use crate::language::Language;
+use rocket::serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Morpheme {
+    pub morpheme: String,
+    pub dictionary_form: String,
+    pub reading: String,
+    pub part_of_speech: PartOfSpeech,
+    pub part_of_speech_subcategories: Vec<String>,
+    pub conjugation_type: Option<String>,
+    pub conjugation_form: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub furigana: Option<Vec<FuriganaSegment>>,
+    pub offsets: MorphemeOffsets,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub features: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq)]
+pub struct MorphemeOffsets {
+    pub utf16: Span,
+    pub scalar: Span,
+}
+
+pub struct Token {
+    pub surface: String,
+    pub byte_start: usize,
+    pub byte_end: usize,
+    pub features: Vec<String>,
+}
+
+pub trait Analyzer: Send + Sync {
+    fn tokenize(&self, text: &str) -> Vec<Token>;
+    fn feature_mapping(&self) -> &dyn FeatureMapping;
+
+    /// Identifies the backend and dictionary producing the analysis, so cached results are never
+    /// served for a different configuration.
+    fn configuration_key(&self) -> String;
+}
+
+pub fn init_analyzer() -> Arc<dyn Analyzer> {
+    match get_analyzer_backend().as_str() {
+        #[cfg(feature = "mecab")]
+        "mecab" => Arc::new(self::mecab::MecabAnalyzer::new()),
+        #[cfg(feature = "lindera")]
+        "lindera" => Arc::new(self::lindera::LinderaAnalyzer::new()),
+        backend => panic!("analyzer backend \"{}\" is not available", backend),
+    }
+}
+
+/// The analyzer of every language this build can analyze. Japanese uses the configured backend,
+/// Chinese is available when the `jieba` feature is enabled.
+pub struct Analyzers {
+    analyzers: BTreeMap<Language, Arc<dyn Analyzer>>,
+}
+
+impl Analyzers {
+    pub fn new(analyzers: BTreeMap<Language, Arc<dyn Analyzer>>) -> Self {
+        Analyzers { analyzers }
+    }
+
+    pub fn get(&self, language: Language) -> Option<&Arc<dyn Analyzer>> {
+        self.analyzers.get(&language)
+    }
+
+    pub fn get_languages(&self) -> Vec<Language> {
+        self.analyzers.keys().copied().collect()
+    }
+}
+
+pub fn init_analyzers() -> Analyzers {
+    let mut analyzers = BTreeMap::new();
+    analyzers.insert(Language::Japanese, init_analyzer());
+
+    #[cfg(feature = "jieba")]
+    analyzers.insert(
+        Language::Chinese,
+        Arc::new(self::jieba::JiebaAnalyzer::new()) as Arc<dyn Analyzer>,
+    );
+
+    Analyzers::new(analyzers)
+}
+
+/// Converts the byte offsets of tokens, given in order, to UTF-16 and scalar offsets, counting
+/// each part of the text once.
+struct OffsetCounter<'a> {
+    text: &'a str,
+    byte_offset: usize,
+    utf16_offset: usize,
+    scalar_offset: usize,
+}
+
+impl<'a> OffsetCounter<'a> {
+    fn new(text: &'a str) -> Self {
+        OffsetCounter {
+            text,
+            byte_offset: 0,
+            utf16_offset: 0,
+            scalar_offset: 0,
+        }
+    }
+
+    fn utf16_and_scalar_offset(&mut self, byte_offset: usize) -> (usize, usize) {
+        let byte_offset = match self.text.get(..byte_offset) {
+            Some(_) => byte_offset,
+            None => self.text.len(),
+        };
+
+        if byte_offset < self.byte_offset {
+            *self = OffsetCounter::new(self.text);
+        }
+
+        let skipped = &self.text[self.byte_offset..byte_offset];
+        self.byte_offset = byte_offset;
+        self.utf16_offset += skipped.encode_utf16().count();
+        self.scalar_offset += skipped.chars().count();
+
+        (self.utf16_offset, self.scalar_offset)
+    }
+
+    fn morpheme_offsets(&mut self, token: &Token) -> MorphemeOffsets {
+        let (utf16_start, scalar_start) = self.utf16_and_scalar_offset(token.byte_start);
+        let (utf16_end, scalar_end) = self.utf16_and_scalar_offset(token.byte_end);
+
+        MorphemeOffsets {
+            utf16: Span {
+                start: utf16_start,
+                end: utf16_end,
+            },
+            scalar: Span {
+                start: scalar_start,
+                end: scalar_end,
+            },
+        }
+    }
+}
+
+pub fn dictionary_form_to_reading(
+    analyzer: &dyn Analyzer,
+    dictionary_form: &str,
+    default: String,
+) -> String {
+    analyzer
+        .tokenize(dictionary_form)
+        .first()
+        .and_then(|token| analyzer.feature_mapping().surface_reading(&token.features))
+        .unwrap_or(default)
+}
+
+pub fn analyze_sentence(analyzer: &dyn Analyzer, sentence: &str) -> Vec<Morpheme> {
+    let feature_mapping = analyzer.feature_mapping();
+    let mut offset_counter = OffsetCounter::new(sentence);
+
+    analyzer
+        .tokenize(sentence)
+        .into_iter()
+        .map(|token| {
+            let offsets = offset_counter.morpheme_offsets(&token);
+            let surface = token.surface;
+            let features = token.features;
+            let surface_reading = feature_mapping.surface_reading(&features);
+            let dictionary_form = feature_mapping
+                .dictionary_form(&features)
+                .unwrap_or_else(|| surface.clone());
+            let reading = match (
+                feature_mapping.dictionary_form_reading(&features),
+                &surface_reading,
+            ) {
+                (Some(reading), _) => reading,
+                (None, None) => surface.clone(),
+                (None, Some(reading)) => {
+                    dictionary_form_to_reading(analyzer, &dictionary_form, reading.clone())
+                }
+            };
+
+            Morpheme {
+                dictionary_form,
+                reading,
+                part_of_speech: feature_mapping.part_of_speech(&features),
+                part_of_speech_subcategories: feature_mapping
+                    .part_of_speech_subcategories(&features),
+                conjugation_type: feature_mapping.conjugation_type(&features),
+                conjugation_form: feature_mapping.conjugation_form(&features),
+                furigana: surface_reading
+                    .map(|surface_reading| segment_furigana(&surface, &surface_reading)),
+                offsets,
+                morpheme: surface,
+                features: Some(features),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,32 @@
+use rocket::serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PartOfSpeech {
+    Noun,
+    Pronoun,
+    AdjectivalNoun,
+    Verb,
+    Adjective,
+    Adverb,
+    Adnominal,
+    Conjunction,
+    Interjection,
+    Particle,
+    AuxiliaryVerb,
+    Prefix,
+    Suffix,
+    Symbol,
+    Filler,
+    Other,
+    Unknown,
+}
+
+impl PartOfSpeech {
+    pub fn is_content_word(self) -> bool {
+        !matches!(
+            self,
+            PartOfSpeech::Particle | PartOfSpeech::AuxiliaryVerb | PartOfSpeech::Symbol
+        )
+    }
+}
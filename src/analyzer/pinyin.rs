@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+const TONE_MARKS: [(char, [char; 4]); 6] = [
+    ('a', ['ā', 'á', 'ǎ', 'à']),
+    ('e', ['ē', 'é', 'ě', 'è']),
+    ('i', ['ī', 'í', 'ǐ', 'ì']),
+    ('o', ['ō', 'ó', 'ǒ', 'ò']),
+    ('u', ['ū', 'ú', 'ǔ', 'ù']),
+    ('ü', ['ǖ', 'ǘ', 'ǚ', 'ǜ']),
+];
+
+fn is_pinyin_vowel(character: char) -> bool {
+    TONE_MARKS.iter().any(|(vowel, _)| *vowel == character)
+}
+
+fn add_tone_mark(vowel: char, tone: usize) -> char {
+    let lowercase_vowel = vowel.to_lowercase().next().unwrap_or(vowel);
+
+    let marked_vowel = TONE_MARKS
+        .iter()
+        .find(|(unmarked_vowel, _)| *unmarked_vowel == lowercase_vowel)
+        .map(|(_, marked_vowels)| marked_vowels[tone - 1])
+        .unwrap_or(vowel);
+
+    if vowel.is_uppercase() {
+        marked_vowel.to_uppercase().next().unwrap_or(marked_vowel)
+    } else {
+        marked_vowel
+    }
+}
+
+/// Converts a syllable written with a tone number, such as `lu:4` or `zhong1`, to tone marks.
+/// The mark goes on `a` or `e` when present, on the `o` of `ou`, and on the last vowel otherwise.
+/// Syllables without a tone number, and the neutral tone `5`, are left unmarked.
+fn numbered_syllable_to_tone_marks(syllable: &str) -> String {
+    let syllable = syllable.replace("u:", "ü").replace("U:", "Ü");
+
+    let (letters, tone) = match syllable.char_indices().last() {
+        Some((index, tone @ '1'..='5')) => (&syllable[..index], tone as usize - '0' as usize),
+        _ => (syllable.as_str(), 5),
+    };
+
+    let characters = letters.chars().collect::<Vec<char>>();
+    let lowercase_characters = characters
+        .iter()
+        .map(|character| character.to_lowercase().next().unwrap_or(*character))
+        .collect::<Vec<char>>();
+
+    let mark_index = if tone == 5 {
+        None
+    } else {
+        lowercase_characters
+            .iter()
+            .position(|character| *character == 'a' || *character == 'e')
+            .or_else(|| {
+                lowercase_characters
+                    .windows(2)
+                    .position(|characters| characters == ['o', 'u'])
+            })
+            .or_else(|| {
+                lowercase_characters
+                    .iter()
+                    .rposition(|character| is_pinyin_vowel(*character))
+            })
+    };
+
+    characters
+        .iter()
+        .enumerate()
+        .map(|(index, character)| match mark_index {
+            Some(mark_index) if mark_index == index => add_tone_mark(*character, tone),
+            _ => *character,
+        })
+        .collect()
+}
+
+/// Joins syllables into a word, adding an apostrophe before syllables starting with `a`, `e` or
+/// `o` so that the syllable boundary stays readable, e.g. `xī'ān`.
+fn join_syllables(syllables: impl Iterator<Item = String>) -> String {
+    let mut word = String::new();
+
+    for syllable in syllables {
+        let starts_with_vowel = syllable
+            .chars()
+            .next()
+            .and_then(|character| character.to_lowercase().next())
+            .is_some_and(|character| {
+                TONE_MARKS
+                    .iter()
+                    .filter(|(vowel, _)| matches!(vowel, 'a' | 'e' | 'o'))
+                    .any(|(vowel, marked_vowels)| {
+                        *vowel == character || marked_vowels.contains(&character)
+                    })
+            });
+
+        if !word.is_empty() && starts_with_vowel {
+            word.push('\'');
+        }
+
+        word.push_str(&syllable);
+    }
+
+    word
+}
+
+/// Converts space-separated pinyin with tone numbers, as found in CC-CEDICT, to a word written
+/// with tone marks, e.g. `Xi1 an1` to `Xī'ān`.
+pub fn numbered_pinyin_to_tone_marks(pinyin: &str) -> String {
+    join_syllables(
+        pinyin
+            .split_whitespace()
+            .map(numbered_syllable_to_tone_marks),
+    )
+}
+
+/// Readings of Chinese words as tone-marked pinyin, read from a CC-CEDICT dictionary.
+#[derive(Default)]
+pub struct PinyinDictionary {
+    readings: HashMap<String, String>,
+}
+
+impl PinyinDictionary {
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Parses CC-CEDICT lines of the form `traditional simplified [pin1 yin1] /definitions/`,
+    /// indexing both the traditional and the simplified form. When a word has several entries the
+    /// first one wins, except that common words are preferred to proper nouns, whose pinyin is
+    /// capitalized.
+    pub fn parse<R: BufRead>(reader: R) -> Result<Self, String> {
+        let mut readings: HashMap<String, String> = HashMap::new();
+
+        for (index, line) in reader.lines().enumerate() {
+            let line = line.map_err(|err| format!("line {}: {}", index + 1, err))?;
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let entry = line.split_once(" [").and_then(|(forms, rest)| {
+                let (traditional, simplified) = forms.split_once(' ')?;
+                let (pinyin, _) = rest.split_once(']')?;
+
+                Some((traditional, simplified, pinyin))
+            });
+
+            let (traditional, simplified, pinyin) = entry.ok_or_else(|| {
+                format!(
+                    "line {} should contain a traditional form, a simplified form and pinyin",
+                    index + 1
+                )
+            })?;
+
+            let reading = numbered_pinyin_to_tone_marks(pinyin);
+            let is_proper_noun = reading.starts_with(char::is_uppercase);
+
+            for form in [traditional, simplified] {
+                let should_replace = match readings.get(form) {
+                    None => true,
+                    Some(existing_reading) => {
+                        !is_proper_noun && existing_reading.starts_with(char::is_uppercase)
+                    }
+                };
+
+                if should_replace {
+                    readings.insert(form.to_string(), reading.clone());
+                }
+            }
+        }
+
+        Ok(PinyinDictionary { readings })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let file = File::open(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+
+        Self::parse(BufReader::new(file)).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    /// Reading of a word, or the readings of its characters joined when the word itself has no
+    /// entry. Words with a character missing from the dictionary have no reading.
+    pub fn get_reading(&self, word: &str) -> Option<String> {
+        if let Some(reading) = self.readings.get(word) {
+            return Some(reading.clone());
+        }
+
+        let readings = word
+            .chars()
+            .map(|character| self.readings.get(character.to_string().as_str()).cloned())
+            .collect::<Option<Vec<String>>>()?;
+
+        Some(join_syllables(readings.into_iter())).filter(|reading| !reading.is_empty())
+    }
+
+    pub fn len(&self) -> usize {
+        self.readings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.readings.is_empty()
+    }
+}
@@ -0,0 +1,72 @@
+const SENTENCE_TERMINATORS: [char; 7] = ['。', '！', '？', '．', '!', '?', '…'];
+const OPENING_QUOTES: [char; 4] = ['「', '『', '（', '('];
+const CLOSING_QUOTES: [char; 4] = ['」', '』', '）', ')'];
+/// Characters after which an open quote is taken to be unbalanced, so that a missing closing
+/// quote does not merge the rest of the paragraph into one sentence.
+const MAXIMUM_QUOTE_LENGTH: usize = 100;
+
+fn is_blank(character: char) -> bool {
+    character.is_whitespace() || character == '\u{3000}'
+}
+
+fn push_sentence(sentences: &mut Vec<String>, sentence: &mut String) {
+    let trimmed = sentence.trim_matches(is_blank);
+
+    if !trimmed.is_empty() {
+        sentences.push(trimmed.to_string());
+    }
+
+    sentence.clear();
+}
+
+/// Splits Japanese text into sentences on terminal punctuation and line breaks. Punctuation inside
+/// 「」, 『』 and parentheses does not end a sentence, unless the quote has gone on for longer than
+/// [`MAXIMUM_QUOTE_LENGTH`], and closing quotes directly after a terminator stay with the sentence
+/// they close.
+pub fn split_sentences(text: &str) -> Vec<String> {
+    let mut sentences = vec![];
+    let mut sentence = String::new();
+    let mut quote_depth: usize = 0;
+    let mut quote_length: usize = 0;
+    let mut is_terminated = false;
+
+    for character in text.chars() {
+        if character == '\n' || character == '\r' {
+            push_sentence(&mut sentences, &mut sentence);
+            quote_depth = 0;
+            quote_length = 0;
+            is_terminated = false;
+            continue;
+        }
+
+        let is_terminator = SENTENCE_TERMINATORS.contains(&character);
+        let is_closing_quote = CLOSING_QUOTES.contains(&character);
+
+        if is_terminated && !is_terminator && !is_closing_quote {
+            push_sentence(&mut sentences, &mut sentence);
+            is_terminated = false;
+        }
+
+        sentence.push(character);
+
+        if quote_depth > 0 {
+            quote_length += 1;
+        }
+
+        if OPENING_QUOTES.contains(&character) {
+            if quote_depth == 0 {
+                quote_length = 0;
+            }
+            quote_depth += 1;
+        } else if is_closing_quote {
+            quote_depth = quote_depth.saturating_sub(1);
+        } else if is_terminator && (quote_depth == 0 || quote_length >= MAXIMUM_QUOTE_LENGTH) {
+            quote_depth = 0;
+            is_terminated = true;
+        }
+    }
+
+    push_sentence(&mut sentences, &mut sentence);
+
+    sentences
+}
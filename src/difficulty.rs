@@ -0,0 +1,121 @@
+use crate::analyzer::furigana::is_kanji;
+use crate::frequency_list::{FrequencyLookup, FrequencyMatch};
+use crate::helpers::get_difficulty_weights;
+use rocket::serde::Serialize;
+
+pub const DEFAULT_DIFFICULTY_WEIGHTS: &str =
+    "frequency:3,unknown_words:2,length:0.05,kanji_density:2";
+
+/// Rank from which a word counts as fully rare. Rarity grows with the logarithm of the rank below.
+const RARE_RANK: f64 = 50000.0;
+
+/// Rarity of a word from 0 for the most frequent word to 1 for words ranked [`RARE_RANK`] or
+/// lower and words missing from the frequency list.
+pub fn word_rarity(frequency_lookup: FrequencyLookup) -> f64 {
+    if frequency_lookup.match_type == FrequencyMatch::NotFound {
+        return 1.0;
+    }
+
+    ((frequency_lookup.frequency as f64 + 1.0).ln() / (RARE_RANK + 1.0).ln()).min(1.0)
+}
+
+/// Weights of the components of a sentence's difficulty score: the mean rarity of its content
+/// words, from 0 to 1, the number of content words the user does not know, its length in
+/// characters and the share of its characters that are kanji, from 0 to 1.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct DifficultyWeights {
+    pub frequency: f64,
+    pub unknown_words: f64,
+    pub length: f64,
+    pub kanji_density: f64,
+}
+
+impl DifficultyWeights {
+    /// Parses comma-separated `component:weight` pairs, e.g. `frequency:3,length:0.05`.
+    /// Components left out keep their default weight.
+    pub fn parse(difficulty_weights: &str) -> Result<Self, String> {
+        let mut weights = Self::default();
+
+        for weight in difficulty_weights.split(',').map(str::trim) {
+            let (component, value) = weight.split_once(':').ok_or_else(|| {
+                format!(
+                    "difficulty weight \"{}\" should be component:weight",
+                    weight
+                )
+            })?;
+            let component = component.trim();
+
+            let value = value
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite() && *value >= 0.0)
+                .ok_or_else(|| {
+                    format!(
+                        "difficulty weight of \"{}\" should be a non-negative number",
+                        component
+                    )
+                })?;
+
+            match component {
+                "frequency" => weights.frequency = value,
+                "unknown_words" => weights.unknown_words = value,
+                "length" => weights.length = value,
+                "kanji_density" => weights.kanji_density = value,
+                _ => return Err(format!("unknown difficulty component \"{}\"", component)),
+            }
+        }
+
+        Ok(weights)
+    }
+
+    /// Difficulty of a sentence, rounded to two decimals. Higher is harder.
+    pub fn score(
+        &self,
+        sentence: &str,
+        content_word_rarities: &[f64],
+        unknown_word_count: usize,
+    ) -> f64 {
+        let characters = sentence
+            .chars()
+            .filter(|character| !character.is_whitespace())
+            .collect::<Vec<char>>();
+        let kanji_count = characters
+            .iter()
+            .filter(|character| is_kanji(**character))
+            .count();
+
+        let mean_rarity = if content_word_rarities.is_empty() {
+            0.0
+        } else {
+            content_word_rarities.iter().sum::<f64>() / content_word_rarities.len() as f64
+        };
+        let kanji_density = if characters.is_empty() {
+            0.0
+        } else {
+            kanji_count as f64 / characters.len() as f64
+        };
+
+        let score = self.frequency * mean_rarity
+            + self.unknown_words * unknown_word_count as f64
+            + self.length * characters.len() as f64
+            + self.kanji_density * kanji_density;
+
+        (score * 100.0).round() / 100.0
+    }
+}
+
+impl Default for DifficultyWeights {
+    fn default() -> Self {
+        DifficultyWeights {
+            frequency: 3.0,
+            unknown_words: 2.0,
+            length: 0.05,
+            kanji_density: 2.0,
+        }
+    }
+}
+
+pub fn init_difficulty_weights() -> DifficultyWeights {
+    DifficultyWeights::parse(&get_difficulty_weights()).expect("difficulty weights should parse")
+}
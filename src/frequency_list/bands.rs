@@ -0,0 +1,97 @@
+use crate::frequency_list::{FrequencyLookup, FrequencyMatch};
+use crate::helpers::get_frequency_bands;
+use rocket::serde::Serialize;
+
+pub const DEFAULT_FREQUENCY_BANDS: &str = "top_1k:1000,1k_5k:5000,5k_10k:10000,rare";
+
+#[derive(Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct FrequencyBand {
+    pub name: String,
+    /// Highest 1-based rank in the band, `None` for the final band holding every rarer word.
+    pub max_rank: Option<usize>,
+}
+
+/// Consecutive rank ranges used to group words by rarity, from most to least frequent.
+pub struct FrequencyBands {
+    bands: Vec<FrequencyBand>,
+}
+
+impl FrequencyBands {
+    /// Parses comma-separated `name:max_rank` bands in ascending order of rank, ending with a band
+    /// without a maximum rank, e.g. `top_1k:1000,1k_5k:5000,rare`.
+    pub fn parse(frequency_bands: &str) -> Result<Self, String> {
+        let mut bands: Vec<FrequencyBand> = vec![];
+
+        for band in frequency_bands.split(',').map(str::trim) {
+            if bands.last().is_some_and(|band| band.max_rank.is_none()) {
+                return Err("only the last frequency band may omit its maximum rank".to_string());
+            }
+
+            let (name, max_rank) = match band.split_once(':') {
+                Some((name, max_rank)) => (
+                    name.trim(),
+                    Some(max_rank.trim().parse::<usize>().map_err(|_| {
+                        format!("frequency band \"{}\" has an invalid maximum rank", name)
+                    })?),
+                ),
+                None => (band, None),
+            };
+
+            if name.is_empty() {
+                return Err("frequency band names should not be empty".to_string());
+            }
+
+            let previous_max_rank = bands.last().and_then(|band| band.max_rank).unwrap_or(0);
+            if max_rank.is_some_and(|max_rank| max_rank <= previous_max_rank) {
+                return Err(format!(
+                    "frequency band \"{}\" should end after the previous band",
+                    name
+                ));
+            }
+
+            bands.push(FrequencyBand {
+                name: name.to_string(),
+                max_rank,
+            });
+        }
+
+        if bands.last().and_then(|band| band.max_rank).is_some() {
+            return Err("the last frequency band should not have a maximum rank".to_string());
+        }
+
+        Ok(FrequencyBands { bands })
+    }
+
+    pub fn get_bands(&self) -> &[FrequencyBand] {
+        &self.bands
+    }
+
+    /// Words missing from the frequency list always fall into the last band, however short the
+    /// list is.
+    pub fn get_band(&self, frequency_lookup: FrequencyLookup) -> &str {
+        let last_band = &self.bands[self.bands.len() - 1];
+
+        if frequency_lookup.match_type == FrequencyMatch::NotFound {
+            return &last_band.name;
+        }
+
+        let rank = frequency_lookup.frequency + 1;
+        let band = self
+            .bands
+            .iter()
+            .find(|band| band.max_rank.is_none_or(|max_rank| rank <= max_rank))
+            .unwrap_or(last_band);
+
+        &band.name
+    }
+}
+
+impl Default for FrequencyBands {
+    fn default() -> Self {
+        Self::parse(DEFAULT_FREQUENCY_BANDS).expect("default frequency bands should parse")
+    }
+}
+
+pub fn init_frequency_bands() -> FrequencyBands {
+    FrequencyBands::parse(&get_frequency_bands()).expect("frequency bands should parse")
+}
@@ -0,0 +1,161 @@
+use crate::frequency_list::normalization::normalize_width;
+use crate::frequency_list::{
+    index_frequency_entries, match_frequency_entries, rank_entries, FrequencyEntry, FrequencyList,
+    FrequencyLookup, FrequencyMatch,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+
+fn fold_pinyin_character(character: char) -> Option<char> {
+    match character {
+        'ā' | 'á' | 'ǎ' | 'à' => Some('a'),
+        'ē' | 'é' | 'ě' | 'è' => Some('e'),
+        'ī' | 'í' | 'ǐ' | 'ì' => Some('i'),
+        'ō' | 'ó' | 'ǒ' | 'ò' => Some('o'),
+        'ū' | 'ú' | 'ǔ' | 'ù' => Some('u'),
+        'ǖ' | 'ǘ' | 'ǚ' | 'ǜ' | 'v' => Some('ü'),
+        '0'..='9' | ' ' | '\'' | '’' | '-' => None,
+        character => Some(character),
+    }
+}
+
+/// Characters of [`normalize_pinyin`], produced on the fly so lookups can compare readings
+/// without allocating.
+pub fn normalized_pinyin_chars(reading: &str) -> impl Iterator<Item = char> + Clone + '_ {
+    let mut characters = reading.chars().flat_map(char::to_lowercase).peekable();
+
+    std::iter::from_fn(move || loop {
+        let character = match characters.next()? {
+            'u' if characters.peek() == Some(&':') => {
+                characters.next();
+                'ü'
+            }
+            character => character,
+        };
+
+        if let Some(character) = fold_pinyin_character(character) {
+            return Some(character);
+        }
+    })
+}
+
+/// Normalizes pinyin so that readings written with tone marks, tone numbers or neither compare
+/// equal, e.g. `Běijīng`, `bei3 jing1` and `beijing`. `v` and `u:` are read as `ü`.
+pub fn normalize_pinyin(reading: &str) -> String {
+    normalized_pinyin_chars(reading).collect()
+}
+
+/// A Chinese frequency list, where readings are pinyin.
+pub struct ZhFrequencyList {
+    lowest_frequency: usize,
+    frequency_hash_map: HashMap<String, Vec<FrequencyEntry>>,
+}
+
+impl ZhFrequencyList {
+    /// A list without any word, for which every word ranks last.
+    pub fn empty() -> Self {
+        Self::from_entries(vec![], 0)
+    }
+
+    /// Parses a list ordered from most to least frequent, whose entries are either a word, a
+    /// `[word]` array or a `[word, pinyin]` array.
+    pub fn from_json(frequency_list_json: &str) -> Result<Self, String> {
+        let frequency_list_json: Value =
+            serde_json::from_str(frequency_list_json).map_err(|err| err.to_string())?;
+        let words = frequency_list_json
+            .as_array()
+            .ok_or_else(|| "frequency list should be an array".to_string())?;
+
+        let mut entries = vec![];
+
+        for (index, word_value) in words.iter().enumerate() {
+            let entry = match word_value {
+                Value::String(dictionary_form) => Some((dictionary_form.as_str(), None)),
+                Value::Array(word) => match (word.first(), word.get(1)) {
+                    (Some(Value::String(dictionary_form)), None) => {
+                        Some((dictionary_form.as_str(), None))
+                    }
+                    (Some(Value::String(dictionary_form)), Some(Value::String(reading))) => {
+                        Some((dictionary_form.as_str(), Some(reading.as_str())))
+                    }
+                    _ => None,
+                },
+                _ => None,
+            };
+
+            let (dictionary_form, reading) = entry.ok_or_else(|| {
+                format!(
+                    "entry {} should be a word or an array of a word and its pinyin",
+                    index
+                )
+            })?;
+
+            entries.push((dictionary_form.to_string(), reading.map(str::to_string)));
+        }
+
+        Ok(Self::from_ranked_entries(entries))
+    }
+
+    /// Builds a list from entries ordered from most to least frequent. Repeated entries keep their
+    /// best rank.
+    pub fn from_ranked_entries(entries: Vec<(String, Option<String>)>) -> Self {
+        let entries = rank_entries(entries);
+        let word_count = entries.len();
+
+        Self::from_entries(entries, word_count)
+    }
+
+    /// Builds a list from entries that already carry their frequency. `word_count` is the size of
+    /// the whole list, so missing words still rank last.
+    pub fn from_entries(entries: Vec<(String, Option<String>, usize)>, word_count: usize) -> Self {
+        ZhFrequencyList {
+            lowest_frequency: word_count + 1,
+            frequency_hash_map: index_frequency_entries(entries.into_iter(), normalize_pinyin),
+        }
+    }
+
+    fn find_frequency(&self, word: &str, reading: Option<&str>) -> FrequencyLookup {
+        self.frequency_hash_map
+            .get(normalize_width(word).as_ref())
+            .and_then(|entries| {
+                match_frequency_entries(
+                    entries.iter().map(FrequencyEntry::view),
+                    word,
+                    reading,
+                    normalized_pinyin_chars,
+                )
+            })
+            .unwrap_or(FrequencyLookup {
+                frequency: self.lowest_frequency,
+                match_type: FrequencyMatch::NotFound,
+            })
+    }
+}
+
+impl FrequencyList for ZhFrequencyList {
+    fn lookup(&self, word: &str, reading: &str) -> FrequencyLookup {
+        self.find_frequency(word, Some(reading))
+    }
+
+    fn lookup_dictionary_form(&self, word: &str) -> FrequencyLookup {
+        self.find_frequency(word, None)
+    }
+
+    fn entries(&self) -> Vec<(String, Option<String>, usize)> {
+        self.frequency_hash_map
+            .values()
+            .flatten()
+            .map(|entry| {
+                (
+                    entry.dictionary_form.clone(),
+                    entry.reading.clone(),
+                    entry.frequency,
+                )
+            })
+            .collect()
+    }
+
+    fn len(&self) -> usize {
+        self.lowest_frequency - 1
+    }
+}
@@ -0,0 +1,58 @@
+use std::io::Read;
+
+/// Reads CSV rows of `dictionary_form,reading,count` or `dictionary_form,count`, such as word
+/// counts taken from a learner's own reading material, as `(dictionary_form, reading)` entries
+/// ordered from most to least frequent, whatever the language of the list. Ties keep their order
+/// in the file. A first row without a numeric count is treated as a header.
+pub fn read_counted_entries<R: Read>(reader: R) -> Result<Vec<(String, Option<String>)>, String> {
+    let mut csv_reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+
+    let mut entries: Vec<(String, Option<String>, u64)> = vec![];
+
+    for (index, record) in csv_reader.records().enumerate() {
+        let record = record.map_err(|err| format!("row {}: {}", index + 1, err))?;
+
+        let (dictionary_form, reading, count) = match (record.len(), record.get(0)) {
+            (2, Some(dictionary_form)) => (dictionary_form, None, &record[1]),
+            (3, Some(dictionary_form)) => (dictionary_form, Some(&record[1]), &record[2]),
+            _ => {
+                return Err(format!(
+                    "row {} should contain a dictionary form, an optional reading and a count",
+                    index + 1
+                ))
+            }
+        };
+
+        let count = match count.parse::<u64>() {
+            Ok(count) => count,
+            Err(_) if index == 0 => continue,
+            Err(_) => return Err(format!("row {} should end with a count", index + 1)),
+        };
+
+        if dictionary_form.is_empty() {
+            return Err(format!(
+                "row {} should contain a dictionary form",
+                index + 1
+            ));
+        }
+
+        entries.push((
+            dictionary_form.to_string(),
+            reading
+                .filter(|reading| !reading.is_empty())
+                .map(str::to_string),
+            count,
+        ));
+    }
+
+    entries.sort_by(|(_, _, lhs), (_, _, rhs)| rhs.cmp(lhs));
+
+    Ok(entries
+        .into_iter()
+        .map(|(dictionary_form, reading, _)| (dictionary_form, reading))
+        .collect())
+}
@@ -0,0 +1,190 @@
+use crate::frequency_list::normalization::{
+    normalize_reading, normalize_width, normalized_reading_chars,
+};
+use crate::frequency_list::{index_frequency_entries, match_frequency_entries, FrequencyEntry};
+use crate::helpers::get_jlpt_list_path;
+use crate::language::Language;
+use rocket::serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+const BUNDLED_JLPT_LIST: &str = include_str!("../../jlpt_lists/jp.json");
+
+/// Level of the Japanese-Language Proficiency Test a word is expected at, from the easiest to the
+/// hardest.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum JlptLevel {
+    N5,
+    N4,
+    N3,
+    N2,
+    N1,
+}
+
+impl JlptLevel {
+    pub const ALL: [JlptLevel; 5] = [
+        JlptLevel::N5,
+        JlptLevel::N4,
+        JlptLevel::N3,
+        JlptLevel::N2,
+        JlptLevel::N1,
+    ];
+
+    /// The level with the given number, e.g. `5` for N5.
+    pub fn from_number(number: u64) -> Option<Self> {
+        match number {
+            5 => Some(JlptLevel::N5),
+            4 => Some(JlptLevel::N4),
+            3 => Some(JlptLevel::N3),
+            2 => Some(JlptLevel::N2),
+            1 => Some(JlptLevel::N1),
+            _ => None,
+        }
+    }
+
+    /// Position of the level in [`JlptLevel::ALL`], so that the smallest index is the easiest
+    /// level.
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl FromStr for JlptLevel {
+    type Err = String;
+
+    /// Parses `N5` to `N1`, in any case, or the bare level number.
+    fn from_str(level: &str) -> Result<Self, Self::Err> {
+        let level = level.trim();
+        let number = level
+            .strip_prefix(['N', 'n'])
+            .unwrap_or(level)
+            .parse::<u64>()
+            .ok();
+
+        number
+            .and_then(JlptLevel::from_number)
+            .ok_or_else(|| format!("\"{}\" is not a JLPT level", level))
+    }
+}
+
+/// Japanese vocabulary tagged with the JLPT level it is expected at. Lookups match words the same
+/// way as [`JpFrequencyList`](crate::frequency_list::JpFrequencyList), and a word listed at several
+/// levels gets the easiest one.
+pub struct JlptList {
+    level_hash_map: HashMap<String, Vec<FrequencyEntry>>,
+}
+
+impl JlptList {
+    /// `jlpt_lists/jp.json`, bundled into the binary.
+    pub fn bundled() -> Self {
+        Self::from_json(BUNDLED_JLPT_LIST).expect("bundled JLPT list should parse")
+    }
+
+    /// Parses a list of `[dictionary_form, reading, level]` entries, where the level is a number
+    /// from 5 to 1 or a name such as `"N3"`.
+    pub fn from_json(jlpt_list_json: &str) -> Result<Self, String> {
+        let jlpt_list_json: Value =
+            serde_json::from_str(jlpt_list_json).map_err(|err| err.to_string())?;
+        let words = jlpt_list_json
+            .as_array()
+            .ok_or_else(|| "JLPT list should be an array".to_string())?;
+
+        let mut entries = vec![];
+
+        for (index, word_value) in words.iter().enumerate() {
+            let word = word_value
+                .as_array()
+                .ok_or_else(|| format!("entry {} should be an array", index))?;
+
+            let level = match word.get(2) {
+                Some(Value::Number(number)) => number.as_u64().and_then(JlptLevel::from_number),
+                Some(Value::String(level)) => level.parse::<JlptLevel>().ok(),
+                _ => None,
+            };
+
+            let (dictionary_form, reading, level) = match (
+                word.first().and_then(Value::as_str),
+                word.get(1).and_then(Value::as_str),
+                level,
+            ) {
+                (Some(dictionary_form), Some(reading), Some(level)) => {
+                    (dictionary_form, reading, level)
+                }
+                _ => {
+                    return Err(format!(
+                        "entry {} should contain a dictionary form, a reading and a JLPT level",
+                        index
+                    ))
+                }
+            };
+
+            entries.push((
+                dictionary_form.to_string(),
+                Some(reading.to_string()),
+                level.index(),
+            ));
+        }
+
+        Ok(JlptList {
+            level_hash_map: index_frequency_entries(entries.into_iter(), normalize_reading),
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let jlpt_list_json =
+            std::fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+
+        Self::from_json(&jlpt_list_json).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    /// Level of a word of the given language, which only Japanese words have. Without a reading,
+    /// or when no entry has the reading, the word is looked up by dictionary form alone.
+    pub fn lookup(
+        &self,
+        language: Language,
+        word: &str,
+        reading: Option<&str>,
+    ) -> Option<JlptLevel> {
+        if language != Language::Japanese {
+            return None;
+        }
+
+        let lookup = match_frequency_entries(
+            self.level_hash_map
+                .get(normalize_width(word).as_ref())?
+                .iter()
+                .map(FrequencyEntry::view),
+            word,
+            reading,
+            normalized_reading_chars,
+        )?;
+
+        JlptLevel::ALL.get(lookup.frequency).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.level_hash_map.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.level_hash_map.is_empty()
+    }
+}
+
+impl Default for JlptList {
+    fn default() -> Self {
+        Self::bundled()
+    }
+}
+
+/// The JLPT list at `JLPT_LIST_PATH`, or the bundled one when it is not set.
+pub fn init_jlpt_list() -> JlptList {
+    match get_jlpt_list_path() {
+        Some(jlpt_list_path) => {
+            JlptList::load(Path::new(&jlpt_list_path)).expect("JLPT list should load")
+        }
+        None => JlptList::bundled(),
+    }
+}
@@ -0,0 +1,570 @@
+pub mod bands;
+pub mod chinese;
+pub mod counts;
+pub mod jlpt;
+pub mod normalization;
+pub mod yomitan;
+
+use crate::frequency_list::chinese::ZhFrequencyList;
+use crate::frequency_list::counts::read_counted_entries;
+use crate::frequency_list::normalization::{
+    normalize_reading, normalize_width, normalized_reading_chars,
+};
+use crate::frequency_list::yomitan::read_yomitan_frequency_dictionary;
+use crate::helpers::{get_default_frequency_list, get_frequency_list_directory};
+use crate::language::Language;
+use crate::models::user::User;
+use rocket::serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::path::Path;
+
+mod bundled {
+    include!(concat!(env!("OUT_DIR"), "/frequency_table.rs"));
+}
+
+pub const BUNDLED_FREQUENCY_LIST_NAME: &str = "jp";
+/// Name of the empty list used for Chinese until a list with this name is provided.
+pub const EMPTY_CHINESE_FREQUENCY_LIST_NAME: &str = "zh";
+
+/// How a word was matched against a frequency list, from most to least reliable.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FrequencyMatch {
+    Exact,
+    Normalized,
+    DictionaryForm,
+    NotFound,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct FrequencyLookup {
+    pub frequency: usize,
+    pub match_type: FrequencyMatch,
+}
+
+/// Ranks the words of one language from most to least frequent. Frequencies are 0-based ranks and
+/// words missing from the list get the lowest frequency, one past the last rank.
+pub trait FrequencyList: Send + Sync {
+    /// Looks a word up by exact dictionary form and reading first, then by normalized reading,
+    /// then by dictionary form alone.
+    fn lookup(&self, word: &str, reading: &str) -> FrequencyLookup;
+
+    /// Looks a word up by dictionary form alone, for callers that do not know its reading.
+    fn lookup_dictionary_form(&self, word: &str) -> FrequencyLookup;
+
+    /// Every `(dictionary_form, reading, frequency)` entry of the list, in no particular order.
+    fn entries(&self) -> Vec<(String, Option<String>, usize)>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get_frequency(&self, word: &str, reading: &str) -> usize {
+        self.lookup(word, reading).frequency
+    }
+}
+
+/// Dictionary form, reading, normalized reading and frequency of an entry.
+type FrequencyEntryView<'a> = (&'a str, Option<&'a str>, Option<&'a str>, usize);
+
+struct FrequencyEntry {
+    dictionary_form: String,
+    reading: Option<String>,
+    normalized_reading: Option<String>,
+    frequency: usize,
+}
+
+impl FrequencyEntry {
+    fn view(&self) -> FrequencyEntryView<'_> {
+        (
+            &self.dictionary_form,
+            self.reading.as_deref(),
+            self.normalized_reading.as_deref(),
+            self.frequency,
+        )
+    }
+}
+
+fn match_frequency_entries<'a, 'r, N>(
+    entries: impl Iterator<Item = FrequencyEntryView<'a>> + Clone,
+    word: &str,
+    reading: Option<&'r str>,
+    normalized_reading_chars: fn(&'r str) -> N,
+) -> Option<FrequencyLookup>
+where
+    N: Iterator<Item = char> + Clone,
+{
+    let lookup = |frequency: Option<usize>, match_type| {
+        frequency.map(|frequency| FrequencyLookup {
+            frequency,
+            match_type,
+        })
+    };
+
+    if let Some(reading) = reading {
+        let exact_frequency = entries
+            .clone()
+            .find(|(dictionary_form, entry_reading, _, _)| {
+                *dictionary_form == word && *entry_reading == Some(reading)
+            })
+            .map(|(_, _, _, frequency)| frequency);
+        if exact_frequency.is_some() {
+            return lookup(exact_frequency, FrequencyMatch::Exact);
+        }
+
+        let normalized_reading = normalized_reading_chars(reading);
+        let normalized_frequency = entries
+            .clone()
+            .filter(|(_, _, entry_normalized_reading, _)| {
+                entry_normalized_reading.is_some_and(|entry_normalized_reading| {
+                    entry_normalized_reading
+                        .chars()
+                        .eq(normalized_reading.clone())
+                })
+            })
+            .map(|(_, _, _, frequency)| frequency)
+            .min();
+        if normalized_frequency.is_some() {
+            return lookup(normalized_frequency, FrequencyMatch::Normalized);
+        }
+    }
+
+    // Entries without a reading apply to every reading, so they take precedence over the
+    // readings that did not match.
+    let dictionary_form_frequency = entries
+        .clone()
+        .filter(|(_, entry_reading, _, _)| entry_reading.is_none())
+        .map(|(_, _, _, frequency)| frequency)
+        .min()
+        .or_else(|| entries.map(|(_, _, _, frequency)| frequency).min());
+
+    lookup(dictionary_form_frequency, FrequencyMatch::DictionaryForm)
+}
+
+enum FrequencyTable {
+    /// `frequency_lists/jp.json`, compiled by the build script into a perfect hash map from
+    /// width-normalized dictionary form to its entries.
+    Bundled,
+    /// Entries grouped by width-normalized dictionary form.
+    Loaded(HashMap<String, Vec<FrequencyEntry>>),
+}
+
+/// Ranks entries ordered from most to least frequent, keeping the best rank of repeated entries.
+fn rank_entries(entries: Vec<(String, Option<String>)>) -> Vec<(String, Option<String>, usize)> {
+    let mut ranks: HashMap<(String, Option<String>), usize> = HashMap::new();
+
+    for entry in entries {
+        let rank = ranks.len();
+
+        if let Entry::Vacant(vacant_entry) = ranks.entry(entry) {
+            vacant_entry.insert(rank);
+        }
+    }
+
+    ranks
+        .into_iter()
+        .map(|((dictionary_form, reading), rank)| (dictionary_form, reading, rank))
+        .collect()
+}
+
+/// Groups `(dictionary_form, reading, frequency)` entries the way lookups expect them.
+fn index_frequency_entries(
+    entries: impl Iterator<Item = (String, Option<String>, usize)>,
+    normalize_reading: fn(&str) -> String,
+) -> HashMap<String, Vec<FrequencyEntry>> {
+    let mut frequency_hash_map: HashMap<String, Vec<FrequencyEntry>> = HashMap::new();
+
+    for (dictionary_form, reading, frequency) in entries {
+        frequency_hash_map
+            .entry(normalize_width(&dictionary_form).into_owned())
+            .or_default()
+            .push(FrequencyEntry {
+                normalized_reading: reading.as_deref().map(normalize_reading),
+                dictionary_form,
+                reading,
+                frequency,
+            });
+    }
+
+    frequency_hash_map
+}
+
+pub struct JpFrequencyList {
+    lowest_frequency: usize,
+    frequency_table: FrequencyTable,
+}
+
+impl JpFrequencyList {
+    pub fn bundled() -> Self {
+        JpFrequencyList {
+            lowest_frequency: bundled::BUNDLED_WORD_COUNT + 1,
+            frequency_table: FrequencyTable::Bundled,
+        }
+    }
+
+    /// Parses a list of `[dictionary_form, reading]` pairs ordered from most to least frequent,
+    /// ranked like [`JpFrequencyList::from_ranked_entries`].
+    pub fn from_json(frequency_list_json: &str) -> Result<Self, String> {
+        let frequency_list_json: Value =
+            serde_json::from_str(frequency_list_json).map_err(|err| err.to_string())?;
+        let words = frequency_list_json
+            .as_array()
+            .ok_or_else(|| "frequency list should be an array".to_string())?;
+
+        let mut entries = vec![];
+
+        for (index, word_value) in words.iter().enumerate() {
+            let word = word_value
+                .as_array()
+                .ok_or_else(|| format!("entry {} should be an array", index))?;
+            let (dictionary_form, reading) = match (
+                word.first().and_then(Value::as_str),
+                word.get(1).and_then(Value::as_str),
+            ) {
+                (Some(dictionary_form), Some(reading)) => (dictionary_form, reading),
+                _ => return Err(format!("entry {} should contain two strings", index)),
+            };
+
+            entries.push((dictionary_form.to_string(), Some(reading.to_string())));
+        }
+
+        Ok(Self::from_ranked_entries(entries))
+    }
+
+    /// Builds a list from entries ordered from most to least frequent. Entries without a reading
+    /// are used for every reading of their dictionary form without an entry of its own. Repeated
+    /// entries keep their best rank.
+    pub fn from_ranked_entries(entries: Vec<(String, Option<String>)>) -> Self {
+        let entries = rank_entries(entries);
+
+        JpFrequencyList {
+            lowest_frequency: entries.len() + 1,
+            frequency_table: FrequencyTable::Loaded(index_frequency_entries(
+                entries.into_iter(),
+                normalize_reading,
+            )),
+        }
+    }
+
+    /// Builds a list from entries that already carry their frequency, such as a subset of a stored
+    /// list. `word_count` is the size of the whole list, so missing words still rank last.
+    pub fn from_entries(entries: Vec<(String, Option<String>, usize)>, word_count: usize) -> Self {
+        JpFrequencyList {
+            lowest_frequency: word_count + 1,
+            frequency_table: FrequencyTable::Loaded(index_frequency_entries(
+                entries.into_iter(),
+                normalize_reading,
+            )),
+        }
+    }
+
+    fn find_frequency(&self, word: &str, reading: Option<&str>) -> Option<FrequencyLookup> {
+        let dictionary_form = normalize_width(word);
+
+        match &self.frequency_table {
+            FrequencyTable::Bundled => match_frequency_entries(
+                bundled::BUNDLED_FREQUENCY_TABLE
+                    .get(dictionary_form.as_ref())?
+                    .iter()
+                    .map(
+                        |(dictionary_form, reading, normalized_reading, frequency)| {
+                            (
+                                *dictionary_form,
+                                Some(*reading),
+                                Some(*normalized_reading),
+                                *frequency as usize,
+                            )
+                        },
+                    ),
+                word,
+                reading,
+                normalized_reading_chars,
+            ),
+            FrequencyTable::Loaded(frequency_hash_map) => match_frequency_entries(
+                frequency_hash_map
+                    .get(dictionary_form.as_ref())?
+                    .iter()
+                    .map(FrequencyEntry::view),
+                word,
+                reading,
+                normalized_reading_chars,
+            ),
+        }
+    }
+
+    fn not_found(&self) -> FrequencyLookup {
+        FrequencyLookup {
+            frequency: self.lowest_frequency,
+            match_type: FrequencyMatch::NotFound,
+        }
+    }
+}
+
+impl FrequencyList for JpFrequencyList {
+    fn lookup(&self, word: &str, reading: &str) -> FrequencyLookup {
+        self.find_frequency(word, Some(reading))
+            .unwrap_or_else(|| self.not_found())
+    }
+
+    fn lookup_dictionary_form(&self, word: &str) -> FrequencyLookup {
+        self.find_frequency(word, None)
+            .unwrap_or_else(|| self.not_found())
+    }
+
+    fn entries(&self) -> Vec<(String, Option<String>, usize)> {
+        match &self.frequency_table {
+            FrequencyTable::Bundled => bundled::BUNDLED_FREQUENCY_TABLE
+                .values()
+                .flat_map(|entries| entries.iter())
+                .map(|(dictionary_form, reading, _, frequency)| {
+                    (
+                        dictionary_form.to_string(),
+                        Some(reading.to_string()),
+                        *frequency as usize,
+                    )
+                })
+                .collect(),
+            FrequencyTable::Loaded(frequency_hash_map) => frequency_hash_map
+                .values()
+                .flatten()
+                .map(|entry| {
+                    (
+                        entry.dictionary_form.clone(),
+                        entry.reading.clone(),
+                        entry.frequency,
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.lowest_frequency - 1
+    }
+}
+
+impl Default for JpFrequencyList {
+    fn default() -> Self {
+        Self::bundled()
+    }
+}
+
+/// Parses a JSON frequency list in the format of the given language.
+pub fn parse_frequency_list_json(
+    language: Language,
+    frequency_list_json: &str,
+) -> Result<Box<dyn FrequencyList>, String> {
+    Ok(match language {
+        Language::Japanese => Box::new(JpFrequencyList::from_json(frequency_list_json)?),
+        Language::Chinese => Box::new(ZhFrequencyList::from_json(frequency_list_json)?),
+    })
+}
+
+pub fn frequency_list_from_ranked_entries(
+    language: Language,
+    entries: Vec<(String, Option<String>)>,
+) -> Box<dyn FrequencyList> {
+    match language {
+        Language::Japanese => Box::new(JpFrequencyList::from_ranked_entries(entries)),
+        Language::Chinese => Box::new(ZhFrequencyList::from_ranked_entries(entries)),
+    }
+}
+
+pub fn frequency_list_from_entries(
+    language: Language,
+    entries: Vec<(String, Option<String>, usize)>,
+    word_count: usize,
+) -> Box<dyn FrequencyList> {
+    match language {
+        Language::Japanese => Box::new(JpFrequencyList::from_entries(entries, word_count)),
+        Language::Chinese => Box::new(ZhFrequencyList::from_entries(entries, word_count)),
+    }
+}
+
+#[derive(Serialize)]
+pub struct FrequencyListInfo {
+    pub name: String,
+    pub word_count: usize,
+    pub is_default: bool,
+}
+
+/// Frequency lists keyed by name.
+type NamedFrequencyLists = BTreeMap<String, Box<dyn FrequencyList>>;
+
+struct LanguageFrequencyLists {
+    default_name: String,
+    frequency_lists: NamedFrequencyLists,
+}
+
+/// Reads the frequency lists of one language from a directory, keyed by file name without
+/// extension. Subdirectories are skipped.
+fn read_frequency_list_directory(
+    language: Language,
+    directory: &Path,
+) -> Result<NamedFrequencyLists, String> {
+    let mut frequency_lists = NamedFrequencyLists::new();
+
+    let entries = std::fs::read_dir(directory)
+        .map_err(|err| format!("{}: {}", directory.display(), err))?
+        .collect::<Vec<_>>();
+
+    for entry in entries {
+        let path = entry.map_err(|err| err.to_string())?.path();
+
+        let name = match path.file_stem().and_then(|file_stem| file_stem.to_str()) {
+            Some(name) => name.to_string(),
+            None => continue,
+        };
+
+        let frequency_list = match (
+            language,
+            path.extension().and_then(|extension| extension.to_str()),
+        ) {
+            (_, Some("json")) => std::fs::read_to_string(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|frequency_list_json| {
+                    parse_frequency_list_json(language, &frequency_list_json)
+                }),
+            (_, Some("csv")) => File::open(&path)
+                .map_err(|err| err.to_string())
+                .and_then(read_counted_entries)
+                .map(|entries| frequency_list_from_ranked_entries(language, entries)),
+            (Language::Japanese, Some("zip")) => File::open(&path)
+                .map_err(|err| err.to_string())
+                .and_then(read_yomitan_frequency_dictionary)
+                .map(|frequency_list| Box::new(frequency_list) as Box<dyn FrequencyList>),
+            _ => continue,
+        }
+        .map_err(|err| format!("{}: {}", path.display(), err))?;
+
+        frequency_lists.insert(name, frequency_list);
+    }
+
+    Ok(frequency_lists)
+}
+
+/// The frequency lists of every language. Japanese has the bundled list plus every list found in
+/// the configured directory, Chinese has an empty placeholder list plus every list found in its
+/// `zh` subdirectory. Lists are keyed by file name without extension: `.json` files hold
+/// `[dictionary_form, reading]` arrays, `.csv` files hold word counts and `.zip` files are
+/// Japanese Yomitan frequency dictionaries. A file named after the bundled or placeholder list
+/// replaces it.
+pub struct FrequencyLists {
+    languages: BTreeMap<Language, LanguageFrequencyLists>,
+}
+
+impl FrequencyLists {
+    pub fn load(directory: Option<&Path>, default_name: &str) -> Result<Self, String> {
+        let mut languages = BTreeMap::new();
+
+        for language in Language::ALL {
+            let (placeholder_name, placeholder_frequency_list): (&str, Box<dyn FrequencyList>) =
+                match language {
+                    Language::Japanese => (
+                        BUNDLED_FREQUENCY_LIST_NAME,
+                        Box::new(JpFrequencyList::bundled()),
+                    ),
+                    Language::Chinese => (
+                        EMPTY_CHINESE_FREQUENCY_LIST_NAME,
+                        Box::new(ZhFrequencyList::empty()),
+                    ),
+                };
+
+            let mut frequency_lists = NamedFrequencyLists::new();
+            frequency_lists.insert(placeholder_name.to_string(), placeholder_frequency_list);
+
+            let language_directory = match language {
+                Language::Japanese => directory.map(Path::to_path_buf),
+                _ => directory
+                    .map(|directory| directory.join(language.code()))
+                    .filter(|directory| directory.is_dir()),
+            };
+
+            if let Some(language_directory) = language_directory {
+                frequency_lists.extend(read_frequency_list_directory(
+                    language,
+                    &language_directory,
+                )?);
+            }
+
+            let default_name = match language {
+                Language::Japanese => default_name,
+                _ => placeholder_name,
+            };
+
+            if !frequency_lists.contains_key(default_name) {
+                return Err(format!(
+                    "default frequency list \"{}\" was not found",
+                    default_name
+                ));
+            }
+
+            languages.insert(
+                language,
+                LanguageFrequencyLists {
+                    default_name: default_name.to_string(),
+                    frequency_lists,
+                },
+            );
+        }
+
+        Ok(FrequencyLists { languages })
+    }
+
+    fn get_language(&self, language: Language) -> &LanguageFrequencyLists {
+        &self.languages[&language]
+    }
+
+    pub fn contains(&self, language: Language, name: &str) -> bool {
+        self.get_language(language)
+            .frequency_lists
+            .contains_key(name)
+    }
+
+    pub fn get_default_name(&self, language: Language) -> &str {
+        &self.get_language(language).default_name
+    }
+
+    /// Falls back to the default list for unknown names, so a list removed from the directory
+    /// does not break users who had selected it.
+    pub fn get(&self, language: Language, name: Option<&str>) -> &dyn FrequencyList {
+        let language_frequency_lists = self.get_language(language);
+
+        name.and_then(|name| language_frequency_lists.frequency_lists.get(name))
+            .unwrap_or_else(|| {
+                &language_frequency_lists.frequency_lists[&language_frequency_lists.default_name]
+            })
+            .as_ref()
+    }
+
+    pub fn get_for_user(&self, user: &User) -> &dyn FrequencyList {
+        self.get(user.language, user.frequency_list.as_deref())
+    }
+
+    pub fn get_infos(&self, language: Language) -> Vec<FrequencyListInfo> {
+        let language_frequency_lists = self.get_language(language);
+
+        language_frequency_lists
+            .frequency_lists
+            .iter()
+            .map(|(name, frequency_list)| FrequencyListInfo {
+                name: name.clone(),
+                word_count: frequency_list.len(),
+                is_default: *name == language_frequency_lists.default_name,
+            })
+            .collect()
+    }
+}
+
+pub fn init_frequency_lists() -> FrequencyLists {
+    FrequencyLists::load(
+        get_frequency_list_directory().as_deref().map(Path::new),
+        &get_default_frequency_list(),
+    )
+    .expect("frequency lists should load")
+}
@@ -0,0 +1,156 @@
+// Shared with the build script, which indexes the bundled frequency list by the same keys, so this
+// module must not depend on the rest of the crate.
+
+use std::borrow::Cow;
+use std::iter::Peekable;
+use std::str::Chars;
+
+const HALF_WIDTH_KATAKANA: &str = "｡｢｣､･ｦｧｨｩｪｫｬｭｮｯｰｱｲｳｴｵｶｷｸｹｺｻｼｽｾｿﾀﾁﾂﾃﾄﾅﾆﾇﾈﾉﾊﾋﾌﾍﾎﾏﾐﾑﾒﾓﾔﾕﾖﾗﾘﾙﾚﾛﾜﾝ";
+const FULL_WIDTH_KATAKANA: &str =
+    "。「」、・ヲァィゥェォャュョッーアイウエオカキクケコサシスセソタチツテトナニヌネノハヒフヘホマミムメモヤユヨラリルレロワン";
+const HALF_WIDTH_VOICED_SOUND_MARK: char = 'ﾞ';
+const HALF_WIDTH_SEMI_VOICED_SOUND_MARK: char = 'ﾟ';
+const LONG_VOWEL_MARK: char = 'ー';
+const VOWEL_ROWS: [(&str, char); 5] = [
+    ("アカサタナハマヤラワガザダバパァャヮヵ", 'ア'),
+    ("イキシチニヒミリギジヂビピィヰ", 'イ'),
+    ("ウクスツヌフムユルグズヅブプゥュヴ", 'ウ'),
+    ("エケセテネヘメレゲゼデベペェヱヶ", 'エ'),
+    ("オコソトノホモヨロヲゴゾドボポォョ", 'オ'),
+];
+
+fn is_width_variant(character: char) -> bool {
+    matches!(character, '\u{FF01}'..='\u{FF5E}' | '\u{3000}' | '\u{FF61}'..='\u{FF9F}')
+}
+
+fn full_width_katakana(character: char) -> Option<char> {
+    HALF_WIDTH_KATAKANA
+        .chars()
+        .position(|half_width| half_width == character)
+        .and_then(|index| FULL_WIDTH_KATAKANA.chars().nth(index))
+}
+
+fn voiced_katakana(character: char, mark: char) -> Option<char> {
+    let offset = match (mark, character) {
+        (HALF_WIDTH_VOICED_SOUND_MARK, 'ウ') => return Some('ヴ'),
+        (HALF_WIDTH_VOICED_SOUND_MARK, 'カ'..='ト')
+        | (HALF_WIDTH_VOICED_SOUND_MARK, 'ハ'..='ホ') => 1,
+        (HALF_WIDTH_SEMI_VOICED_SOUND_MARK, 'ハ'..='ホ') => 2,
+        _ => return None,
+    };
+
+    // Voiceable kana alternate with their voiced forms, except for the small ッ before ツ.
+    let is_voiceable = match character {
+        'カ'..='チ' => (character as u32 - 'カ' as u32).is_multiple_of(2),
+        'ツ'..='ト' => (character as u32 - 'ツ' as u32).is_multiple_of(2),
+        'ハ'..='ホ' => (character as u32 - 'ハ' as u32).is_multiple_of(3),
+        _ => false,
+    };
+
+    if is_voiceable {
+        char::from_u32(character as u32 + offset)
+    } else {
+        None
+    }
+}
+
+/// Characters of a text with full-width ASCII folded to ASCII and half-width katakana to
+/// full-width katakana, produced on the fly.
+#[derive(Clone)]
+pub struct WidthNormalizedChars<'a> {
+    characters: Peekable<Chars<'a>>,
+}
+
+impl<'a> WidthNormalizedChars<'a> {
+    pub fn new(text: &'a str) -> Self {
+        WidthNormalizedChars {
+            characters: text.chars().peekable(),
+        }
+    }
+}
+
+impl Iterator for WidthNormalizedChars<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        let character = self.characters.next()?;
+
+        Some(match character {
+            '\u{FF01}'..='\u{FF5E}' => {
+                char::from_u32(character as u32 - 0xFEE0).unwrap_or(character)
+            }
+            '\u{3000}' => ' ',
+            HALF_WIDTH_VOICED_SOUND_MARK => '゛',
+            HALF_WIDTH_SEMI_VOICED_SOUND_MARK => '゜',
+            _ => {
+                let full_width = full_width_katakana(character).unwrap_or(character);
+
+                match self
+                    .characters
+                    .peek()
+                    .and_then(|mark| voiced_katakana(full_width, *mark))
+                {
+                    Some(voiced) => {
+                        self.characters.next();
+                        voiced
+                    }
+                    None => full_width,
+                }
+            }
+        })
+    }
+}
+
+/// Folds full-width ASCII to ASCII and half-width katakana to full-width katakana. Text without
+/// width variants is borrowed unchanged.
+pub fn normalize_width(text: &str) -> Cow<'_, str> {
+    if !text.chars().any(is_width_variant) {
+        return Cow::Borrowed(text);
+    }
+
+    Cow::Owned(WidthNormalizedChars::new(text).collect())
+}
+
+fn to_katakana(character: char) -> char {
+    match character {
+        'ぁ'..='ゖ' => char::from_u32(character as u32 + 0x60).unwrap_or(character),
+        _ => character,
+    }
+}
+
+pub fn hiragana_to_katakana(text: &str) -> String {
+    text.chars().map(to_katakana).collect()
+}
+
+fn get_vowel(character: char) -> Option<char> {
+    VOWEL_ROWS
+        .iter()
+        .find(|(row, _)| row.contains(character))
+        .map(|(_, vowel)| *vowel)
+}
+
+/// Characters of [`normalize_reading`], produced on the fly so lookups can compare readings
+/// without allocating.
+pub fn normalized_reading_chars(reading: &str) -> impl Iterator<Item = char> + Clone + '_ {
+    let mut previous_vowel = None;
+
+    WidthNormalizedChars::new(reading)
+        .map(to_katakana)
+        .map(move |character| {
+            if character == LONG_VOWEL_MARK {
+                if let Some(vowel) = previous_vowel {
+                    return vowel;
+                }
+            } else {
+                previous_vowel = get_vowel(character);
+            }
+
+            character
+        })
+}
+
+/// Normalizes a reading to katakana with full-width characters and long vowel marks spelled out
+/// as the vowel they extend, so `らーめん`, `ﾗｰﾒﾝ` and `ラアメン` compare equal.
+pub fn normalize_reading(reading: &str) -> String {
+    normalized_reading_chars(reading).collect()
+}
@@ -0,0 +1,124 @@
+use crate::frequency_list::normalization::hiragana_to_katakana;
+use crate::frequency_list::JpFrequencyList;
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::io::{Read, Seek};
+use zip::ZipArchive;
+
+const INDEX_FILE_NAME: &str = "index.json";
+const TERM_META_BANK_PREFIX: &str = "term_meta_bank_";
+const FREQUENCY_META_MODE: &str = "freq";
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum FrequencyMode {
+    RankBased,
+    OccurrenceBased,
+}
+
+fn read_json_file<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    name: &str,
+) -> Result<Value, String> {
+    let mut file = archive
+        .by_name(name)
+        .map_err(|err| format!("{}: {}", name, err))?;
+    let mut content = String::new();
+    file.read_to_string(&mut content)
+        .map_err(|err| format!("{}: {}", name, err))?;
+
+    serde_json::from_str(&content).map_err(|err| format!("{}: {}", name, err))
+}
+
+fn get_term_meta_bank_number(name: &str) -> Option<usize> {
+    name.strip_prefix(TERM_META_BANK_PREFIX)?
+        .strip_suffix(".json")?
+        .parse()
+        .ok()
+}
+
+/// A frequency value is a number, a numeric string or a `{value, displayValue}` object.
+fn parse_frequency_value(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(string) => string.trim().parse().ok(),
+        Value::Object(object) => object.get("value").and_then(parse_frequency_value),
+        _ => None,
+    }
+}
+
+/// Frequency data is either a bare value, applying to every reading of the term, or a
+/// `{reading, frequency}` object. Readings are stored in hiragana and converted to the katakana
+/// the analyzer produces.
+fn parse_frequency_data(data: &Value) -> Option<(Option<String>, f64)> {
+    match data.get("reading").and_then(Value::as_str) {
+        Some(reading) => Some((
+            Some(hiragana_to_katakana(reading)),
+            parse_frequency_value(data.get("frequency")?)?,
+        )),
+        None => Some((None, parse_frequency_value(data)?)),
+    }
+}
+
+/// Reads a Yomitan (or Yomichan) frequency dictionary archive. Values are ranks unless `index.json`
+/// declares the dictionary `occurrence-based`, in which case higher values rank first.
+pub fn read_yomitan_frequency_dictionary<R: Read + Seek>(
+    reader: R,
+) -> Result<JpFrequencyList, String> {
+    let mut archive = ZipArchive::new(reader).map_err(|err| err.to_string())?;
+
+    let index = read_json_file(&mut archive, INDEX_FILE_NAME)?;
+    let frequency_mode = match index.get("frequencyMode").and_then(Value::as_str) {
+        Some("occurrence-based") => FrequencyMode::OccurrenceBased,
+        _ => FrequencyMode::RankBased,
+    };
+
+    let mut term_meta_bank_names = archive
+        .file_names()
+        .filter_map(|name| Some((get_term_meta_bank_number(name)?, name.to_string())))
+        .collect::<Vec<(usize, String)>>();
+    term_meta_bank_names.sort();
+
+    let mut entries: Vec<(String, Option<String>, f64)> = vec![];
+
+    for (_, name) in term_meta_bank_names {
+        let term_meta_bank = read_json_file(&mut archive, &name)?;
+        let rows = term_meta_bank
+            .as_array()
+            .ok_or_else(|| format!("{} should be an array", name))?;
+
+        for row in rows {
+            let (term, mode, data) = match (
+                row.get(0).and_then(Value::as_str),
+                row.get(1).and_then(Value::as_str),
+                row.get(2),
+            ) {
+                (Some(term), Some(mode), Some(data)) => (term, mode, data),
+                _ => return Err(format!("{} contains a malformed entry", name)),
+            };
+
+            if mode != FREQUENCY_META_MODE {
+                continue;
+            }
+
+            if let Some((reading, value)) = parse_frequency_data(data) {
+                entries.push((term.to_string(), reading, value));
+            }
+        }
+    }
+
+    entries.sort_by(|lhs, rhs| {
+        let ordering = lhs.2.partial_cmp(&rhs.2).unwrap_or(Ordering::Equal);
+
+        match frequency_mode {
+            FrequencyMode::RankBased => ordering,
+            FrequencyMode::OccurrenceBased => ordering.reverse(),
+        }
+    });
+
+    Ok(JpFrequencyList::from_ranked_entries(
+        entries
+            .into_iter()
+            .map(|(term, reading, _)| (term, reading))
+            .collect(),
+    ))
+}
@@ -1,3 +1,6 @@
+use crate::difficulty::DEFAULT_DIFFICULTY_WEIGHTS;
+use crate::frequency_list::bands::DEFAULT_FREQUENCY_BANDS;
+
 fn get_int_env_with_default(name: &str, default: u64) -> u64 {
     match std::env::var(name) {
         Ok(seconds) => seconds.parse::<u64>().unwrap_or(default),
@@ -5,6 +8,17 @@ fn get_int_env_with_default(name: &str, default: u64) -> u64 {
     }
 }
 
+fn get_bool_env_with_default(name: &str, default: bool) -> bool {
+    match std::env::var(name) {
+        Ok(value) => value.parse::<bool>().unwrap_or(default),
+        Err(_) => default,
+    }
+}
+
+fn get_string_env_with_default(name: &str, default: &str) -> String {
+    std::env::var(name).unwrap_or_else(|_| default.to_string())
+}
+
 pub fn get_access_token_expiry_time() -> u64 {
     get_int_env_with_default("JWT_ACCESS_TOKEN_EXPIRY_TIME", 3600)
 }
@@ -13,6 +27,92 @@ pub fn get_refresh_token_expiry_time() -> u64 {
     get_int_env_with_default("JWT_REFRESH_TOKEN_EXPIRY_TIME", 15770000)
 }
 
+pub fn get_registration_mode() -> String {
+    get_string_env_with_default("REGISTRATION_MODE", "closed")
+}
+
 pub fn get_maximum_pending_sentences() -> u64 {
     get_int_env_with_default("MAXIMUM_PENDING_SENTENCES", 250)
 }
+
+pub fn get_mecab_pool_size() -> u32 {
+    get_int_env_with_default("MECAB_POOL_SIZE", 4) as u32
+}
+
+pub fn get_analyzer_backend() -> String {
+    // Builds without libmecab fall back to Lindera, which bundles its dictionary.
+    let default_backend = if cfg!(feature = "mecab") {
+        "mecab"
+    } else {
+        "lindera"
+    };
+
+    get_string_env_with_default("ANALYZER_BACKEND", default_backend)
+}
+
+pub fn get_mecab_dictionary_path() -> Option<String> {
+    std::env::var("MECAB_DICTIONARY_PATH")
+        .ok()
+        .filter(|dictionary_path| !dictionary_path.is_empty())
+}
+
+pub fn get_dictionary_flavor() -> String {
+    get_string_env_with_default("DICTIONARY_FLAVOR", "ipadic")
+}
+
+pub fn get_lindera_dictionary() -> String {
+    get_string_env_with_default("LINDERA_DICTIONARY", "embedded://ipadic")
+}
+
+pub fn get_chinese_dictionary_path() -> Option<String> {
+    std::env::var("CHINESE_DICTIONARY_PATH")
+        .ok()
+        .filter(|dictionary_path| !dictionary_path.is_empty())
+}
+
+pub fn get_jlpt_list_path() -> Option<String> {
+    std::env::var("JLPT_LIST_PATH")
+        .ok()
+        .filter(|jlpt_list_path| !jlpt_list_path.is_empty())
+}
+
+pub fn get_kanji_list_path() -> Option<String> {
+    std::env::var("KANJI_LIST_PATH")
+        .ok()
+        .filter(|kanji_list_path| !kanji_list_path.is_empty())
+}
+
+pub fn get_analysis_cache_size() -> usize {
+    get_int_env_with_default("ANALYSIS_CACHE_SIZE", 10000) as usize
+}
+
+pub fn get_analysis_cache_persistent() -> bool {
+    get_bool_env_with_default("ANALYSIS_CACHE_PERSISTENT", false)
+}
+
+/// Seconds a persisted analysis is kept, or 0 to keep it forever.
+pub fn get_analysis_cache_max_age() -> u64 {
+    get_int_env_with_default("ANALYSIS_CACHE_MAX_AGE", 30 * 24 * 60 * 60)
+}
+
+pub fn get_frequency_list_directory() -> Option<String> {
+    std::env::var("FREQUENCY_LIST_DIRECTORY")
+        .ok()
+        .filter(|frequency_list_directory| !frequency_list_directory.is_empty())
+}
+
+pub fn get_default_frequency_list() -> String {
+    get_string_env_with_default("DEFAULT_FREQUENCY_LIST", "jp")
+}
+
+pub fn get_maximum_frequency_list_size() -> u64 {
+    get_int_env_with_default("MAXIMUM_FREQUENCY_LIST_SIZE", 16 * 1024 * 1024)
+}
+
+pub fn get_difficulty_weights() -> String {
+    get_string_env_with_default("DIFFICULTY_WEIGHTS", DEFAULT_DIFFICULTY_WEIGHTS)
+}
+
+pub fn get_frequency_bands() -> String {
+    get_string_env_with_default("FREQUENCY_BANDS", DEFAULT_FREQUENCY_BANDS)
+}
@@ -1,4 +1,5 @@
 use crate::helpers::{get_access_token_expiry_time, get_refresh_token_expiry_time};
+use crate::models::session::Session;
 use crate::models::user::User;
 use crate::responses::ErrorResponse;
 use diesel::PgConnection;
@@ -32,14 +33,27 @@ pub struct TokenClaims {
     pub sub: i32,
     pub gen: i32,
     pub typ: TokenType,
+    /// Session the token was issued for. Tokens issued before sessions existed have none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<i32>,
+    /// Identifier of a refresh token within its session, replaced on every refresh.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jti: Option<String>,
 }
 
-pub fn generate_token(user: &User, token_type: TokenType) -> Option<String> {
+pub fn generate_token(
+    user: &User,
+    token_type: TokenType,
+    session: Option<&Session>,
+) -> Option<String> {
     let jwt_secret_hmac = get_jwt_secret_hmac();
     let current_timestamp = get_current_timestamp();
-    let expiry_time = match token_type {
-        TokenType::Access => get_access_token_expiry_time(),
-        TokenType::Refresh => get_refresh_token_expiry_time(),
+    let (expiry_time, refresh_token_id) = match token_type {
+        TokenType::Access => (get_access_token_expiry_time(), None),
+        TokenType::Refresh => (
+            get_refresh_token_expiry_time(),
+            session.and_then(|session| session.refresh_token_id.clone()),
+        ),
     };
 
     let claims = TokenClaims {
@@ -48,6 +62,8 @@ pub fn generate_token(user: &User, token_type: TokenType) -> Option<String> {
         sub: user.id,
         gen: user.token_generation,
         typ: token_type,
+        sid: session.map(|session| session.id),
+        jti: refresh_token_id,
     };
 
     claims.sign_with_key(&jwt_secret_hmac).ok()
@@ -63,12 +79,24 @@ pub enum TokenError {
     Revoked,
     InvalidSubject,
     InvalidType,
+    RevokedSession,
+    Reused,
+    InsufficientScope,
+    DatabaseError,
 }
 
 impl TokenError {
+    pub fn status(self) -> Status {
+        match self {
+            TokenError::InsufficientScope => Status::Forbidden,
+            TokenError::DatabaseError => Status::InternalServerError,
+            _ => Status::Unauthorized,
+        }
+    }
+
     pub fn outcome(self, request: &Request) -> Outcome<User, TokenError> {
         request.local_cache(|| self);
-        Outcome::Failure((Status::Unauthorized, self))
+        Outcome::Failure((self.status(), self))
     }
 }
 
@@ -77,6 +105,28 @@ pub fn validate_token(
     token_type: TokenType,
     database_connection: &PgConnection,
 ) -> Result<User, TokenError> {
+    let (user, claims) = validate_token_claims(token, token_type, database_connection)?;
+
+    // Tokens issued before sessions existed have none, and are revoked by generation alone.
+    match claims.sid {
+        Some(session_id)
+            if user
+                .get_session_by_id(database_connection, session_id)
+                .is_none() =>
+        {
+            Err(TokenError::RevokedSession)
+        }
+        _ => Ok(user),
+    }
+}
+
+/// Validates a token like [`validate_token`], also returning its claims. Its session is left to
+/// the caller.
+pub fn validate_token_claims(
+    token: String,
+    token_type: TokenType,
+    database_connection: &PgConnection,
+) -> Result<(User, TokenClaims), TokenError> {
     let jwt_secret_hmac = get_jwt_secret_hmac();
     let claims: TokenClaims = token
         .verify_with_key(&jwt_secret_hmac)
@@ -102,7 +152,7 @@ pub fn validate_token(
         return Err(TokenError::Revoked);
     }
 
-    Ok(user)
+    Ok((user, claims))
 }
 
 pub fn token_error_to_response(token_error: &TokenError) -> ErrorResponse {
@@ -114,6 +164,9 @@ pub fn token_error_to_response(token_error: &TokenError) -> ErrorResponse {
         TokenError::Revoked => "Revoked Token Provided",
         TokenError::InvalidSubject => "Token with Invalid Subject Provided",
         TokenError::InvalidType => "Token with Invalid Type Provided",
+        TokenError::RevokedSession => "Token of Revoked Session Provided",
+        TokenError::Reused => "Reused Token Provided",
+        TokenError::InsufficientScope => "Token with Insufficient Scope Provided",
         _ => {
             return ErrorResponse::error(
                 "Unexpected Token Error".to_string(),
@@ -122,7 +175,7 @@ pub fn token_error_to_response(token_error: &TokenError) -> ErrorResponse {
         }
     };
 
-    ErrorResponse::fail(message.to_string(), Status::Unauthorized)
+    ErrorResponse::fail(message.to_string(), token_error.status())
 }
 
 pub fn extract_access_token_from_header(authorization_header: String) -> Option<String> {
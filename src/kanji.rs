@@ -0,0 +1,142 @@
+use crate::analyzer::furigana::is_kanji;
+use crate::frequency_list::jlpt::JlptLevel;
+use crate::helpers::get_kanji_list_path;
+use rocket::serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+
+const BUNDLED_KANJI_LIST: &str = include_str!("../kanji_lists/jp.json");
+
+/// Grade the Jōyō kanji taught in secondary school are listed at.
+pub const SECONDARY_SCHOOL_GRADE: u8 = 8;
+
+/// The distinct kanji of a text, in order of appearance. Iteration marks and the small
+/// katakana counters count as kanji for furigana but are not characters of their own.
+pub fn extract_kanji(text: &str) -> Vec<String> {
+    let mut kanji: Vec<String> = vec![];
+
+    for character in text.chars() {
+        if !is_kanji(character) || matches!(character, '々' | '〆' | 'ヵ' | 'ヶ') {
+            continue;
+        }
+
+        let character = character.to_string();
+        if !kanji.contains(&character) {
+            kanji.push(character);
+        }
+    }
+
+    kanji
+}
+
+#[derive(Serialize, Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub struct KanjiInfo {
+    /// Jōyō grade, from 1 to 6 for the kanji taught in elementary school and
+    /// [`SECONDARY_SCHOOL_GRADE`] for the others.
+    pub grade: Option<u8>,
+    pub jlpt_level: Option<JlptLevel>,
+}
+
+/// Jōyō grade and JLPT level of kanji.
+pub struct KanjiList {
+    kanji_hash_map: HashMap<String, KanjiInfo>,
+}
+
+impl KanjiList {
+    /// `kanji_lists/jp.json`, bundled into the binary.
+    pub fn bundled() -> Self {
+        Self::from_json(BUNDLED_KANJI_LIST).expect("bundled kanji list should parse")
+    }
+
+    /// Parses a list of `[kanji, grade, jlpt_level]` entries, where the grade and the JLPT level
+    /// may be `null` and the level is a number from 5 to 1 or a name such as `"N3"`.
+    pub fn from_json(kanji_list_json: &str) -> Result<Self, String> {
+        let kanji_list_json: Value =
+            serde_json::from_str(kanji_list_json).map_err(|err| err.to_string())?;
+        let entries = kanji_list_json
+            .as_array()
+            .ok_or_else(|| "kanji list should be an array".to_string())?;
+
+        let mut kanji_hash_map = HashMap::new();
+
+        for (index, entry_value) in entries.iter().enumerate() {
+            let entry = entry_value
+                .as_array()
+                .ok_or_else(|| format!("entry {} should be an array", index))?;
+
+            let kanji = entry
+                .first()
+                .and_then(Value::as_str)
+                .filter(|kanji| kanji.chars().count() == 1);
+            let grade = match entry.get(1) {
+                Some(Value::Null) => Some(None),
+                Some(Value::Number(grade)) => grade
+                    .as_u64()
+                    .filter(|grade| {
+                        (1..=6).contains(grade) || *grade == SECONDARY_SCHOOL_GRADE as u64
+                    })
+                    .map(|grade| Some(grade as u8)),
+                _ => None,
+            };
+            let jlpt_level = match entry.get(2) {
+                Some(Value::Null) => Some(None),
+                Some(Value::Number(level)) => {
+                    level.as_u64().and_then(JlptLevel::from_number).map(Some)
+                }
+                Some(Value::String(level)) => level.parse::<JlptLevel>().ok().map(Some),
+                _ => None,
+            };
+
+            let (kanji, grade, jlpt_level) = match (kanji, grade, jlpt_level) {
+                (Some(kanji), Some(grade), Some(jlpt_level)) => (kanji, grade, jlpt_level),
+                _ => {
+                    return Err(format!(
+                        "entry {} should contain a kanji, a Jōyō grade and a JLPT level",
+                        index
+                    ))
+                }
+            };
+
+            kanji_hash_map.insert(kanji.to_string(), KanjiInfo { grade, jlpt_level });
+        }
+
+        Ok(KanjiList { kanji_hash_map })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let kanji_list_json =
+            std::fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+
+        Self::from_json(&kanji_list_json).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    /// Grade and level of a kanji, both empty for kanji missing from the list.
+    pub fn get(&self, kanji: &str) -> KanjiInfo {
+        self.kanji_hash_map.get(kanji).copied().unwrap_or_default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.kanji_hash_map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.kanji_hash_map.is_empty()
+    }
+}
+
+impl Default for KanjiList {
+    fn default() -> Self {
+        Self::bundled()
+    }
+}
+
+/// The kanji list at `KANJI_LIST_PATH`, or the bundled one when it is not set.
+pub fn init_kanji_list() -> KanjiList {
+    match get_kanji_list_path() {
+        Some(kanji_list_path) => {
+            KanjiList::load(Path::new(&kanji_list_path)).expect("kanji list should load")
+        }
+        None => KanjiList::bundled(),
+    }
+}
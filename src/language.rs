@@ -0,0 +1,64 @@
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::Pg;
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::Text;
+use rocket::serde::{Deserialize, Serialize};
+use std::io::Write;
+
+/// A language users can study, stored and exchanged as its ISO 639-1 code.
+#[derive(
+    Serialize,
+    Deserialize,
+    AsExpression,
+    FromSqlRow,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Default,
+)]
+#[sql_type = "Text"]
+pub enum Language {
+    #[default]
+    #[serde(rename = "ja")]
+    Japanese,
+    #[serde(rename = "zh")]
+    Chinese,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::Japanese, Language::Chinese];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::Japanese => "ja",
+            Language::Chinese => "zh",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Language::ALL
+            .iter()
+            .find(|language| language.code() == code)
+            .copied()
+    }
+}
+
+impl ToSql<Text, Pg> for Language {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+        out.write_all(self.code().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for Language {
+    fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+        let code = <String as FromSql<Text, Pg>>::from_sql(bytes)?;
+
+        Language::from_code(&code).ok_or_else(|| format!("unknown language \"{}\"", code).into())
+    }
+}
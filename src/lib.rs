@@ -3,37 +3,98 @@ extern crate rocket;
 #[macro_use]
 extern crate diesel;
 
-use crate::frequency_list::JpFrequencyList;
+use crate::analyzer::cache::init_analysis_cache;
+use crate::analyzer::init_analyzers;
+use crate::difficulty::init_difficulty_weights;
+use crate::frequency_list::bands::init_frequency_bands;
+use crate::frequency_list::init_frequency_lists;
+use crate::frequency_list::jlpt::init_jlpt_list;
+use crate::kanji::init_kanji_list;
+use crate::registration::{init_registration_mode, RegistrationMode};
 use rocket::{Build, Rocket};
 
-mod analyzer;
+pub mod analyzer;
 mod database;
+pub mod difficulty;
 mod field_validator;
-mod frequency_list;
+pub mod frequency_list;
 pub mod helpers;
 pub mod jwt;
+pub mod kanji;
+pub mod language;
 pub mod models;
+pub mod registration;
 pub mod responses;
 pub mod routes;
 pub mod schema;
+pub mod scope;
 
 pub fn rocket(database_url: &str) -> Rocket<Build> {
     dotenv::dotenv().ok();
 
+    rocket_with_registration_mode(database_url, init_registration_mode())
+}
+
+/// The application with the given registration mode rather than the configured one.
+pub fn rocket_with_registration_mode(
+    database_url: &str,
+    registration_mode: RegistrationMode,
+) -> Rocket<Build> {
+    dotenv::dotenv().ok();
+
     let database_pool = database::init_pool(database_url.to_string());
-    let frequency_list = JpFrequencyList::new();
+    let frequency_lists = init_frequency_lists();
+    let frequency_bands = init_frequency_bands();
+    let jlpt_list = init_jlpt_list();
+    let kanji_list = init_kanji_list();
+    let difficulty_weights = init_difficulty_weights();
+    let analyzers = init_analyzers();
+    let analysis_cache = init_analysis_cache();
 
     rocket::build()
         .manage(database_pool)
-        .manage(frequency_list)
+        .manage(registration_mode)
+        .manage(frequency_lists)
+        .manage(frequency_bands)
+        .manage(jlpt_list)
+        .manage(kanji_list)
+        .manage(difficulty_weights)
+        .manage(analyzers)
+        .manage(analysis_cache)
         .mount(
             "/",
             routes![
                 routes::analyzer::analyze,
-                // routes::authentication::register,
+                routes::analyzer::evaluate,
+                routes::analyzer::analyze_text,
+                routes::analyzer::get_analysis_cache_statistics,
+                routes::frequency_lists::get_all,
+                routes::frequency_lists::select,
+                routes::frequency_lists::get_bands,
+                routes::frequency_lists::lookup,
+                routes::frequency_lists::lookup_many,
+                routes::frequency_lists::upload_custom_json,
+                routes::frequency_lists::upload_custom_csv,
+                routes::frequency_lists::get_custom,
+                routes::frequency_lists::delete_custom,
+                routes::languages::get_all,
+                routes::languages::select,
+                routes::kanji::get_all,
+                routes::kanji::get,
+                routes::authentication::register,
                 routes::authentication::login,
                 routes::authentication::refresh,
+                routes::authentication::logout_all,
+                routes::authentication::change_password,
                 routes::authentication::me,
+                routes::sessions::get_all,
+                routes::sessions::delete,
+                routes::personal_access_tokens::new,
+                routes::personal_access_tokens::get_all,
+                routes::personal_access_tokens::delete,
+                routes::invites::new,
+                routes::invites::get_all,
+                routes::invites::delete,
                 routes::sentences::new,
                 routes::sentences::get,
                 routes::sentences::delete,
@@ -0,0 +1,104 @@
+use crate::analyzer::Morpheme;
+use crate::diesel::ExpressionMethods;
+use crate::diesel::QueryDsl;
+use crate::schema::analysis_cache_entries;
+use chrono::{Duration, NaiveDateTime, Utc};
+use diesel::pg::upsert::excluded;
+use diesel::pg::PgConnection;
+use diesel::result::Error;
+use diesel::RunQueryDsl;
+use std::collections::HashMap;
+
+#[derive(Queryable, Identifiable, PartialEq, Debug)]
+#[primary_key(configuration_key, sentence)]
+#[table_name = "analysis_cache_entries"]
+pub struct AnalysisCacheEntry {
+    pub configuration_key: String,
+    pub sentence: String,
+    pub morphemes: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[table_name = "analysis_cache_entries"]
+pub struct NewAnalysisCacheEntry<'a> {
+    pub configuration_key: &'a str,
+    pub sentence: &'a str,
+    pub morphemes: String,
+}
+
+impl AnalysisCacheEntry {
+    /// Saves the analyses of several sentences in one statement, then prunes the entries older
+    /// than `max_age`, if any.
+    pub fn save_all(
+        database_connection: &PgConnection,
+        configuration_key: &str,
+        analyses: &[(&str, &[Morpheme])],
+        max_age: Option<Duration>,
+    ) -> Result<(), Error> {
+        let new_entries = analyses
+            .iter()
+            .map(|(sentence, morphemes)| {
+                Ok(NewAnalysisCacheEntry {
+                    configuration_key,
+                    sentence,
+                    morphemes: serde_json::to_string(morphemes)
+                        .map_err(|err| Error::SerializationError(Box::new(err)))?,
+                })
+            })
+            .collect::<Result<Vec<NewAnalysisCacheEntry>, Error>>()?;
+
+        if !new_entries.is_empty() {
+            diesel::insert_into(analysis_cache_entries::table)
+                .values(&new_entries)
+                .on_conflict((
+                    analysis_cache_entries::configuration_key,
+                    analysis_cache_entries::sentence,
+                ))
+                .do_update()
+                .set((
+                    analysis_cache_entries::morphemes
+                        .eq(excluded(analysis_cache_entries::morphemes)),
+                    analysis_cache_entries::created_at
+                        .eq(excluded(analysis_cache_entries::created_at)),
+                ))
+                .execute(database_connection)?;
+        }
+
+        if let Some(max_age) = max_age {
+            diesel::delete(
+                analysis_cache_entries::table
+                    .filter(analysis_cache_entries::created_at.lt(Utc::now() - max_age)),
+            )
+            .execute(database_connection)?;
+        }
+
+        Ok(())
+    }
+
+    /// Morphemes of the cached sentences among `sentences`, fetched in one query.
+    pub fn find_all_morphemes(
+        database_connection: &PgConnection,
+        configuration_key: &str,
+        sentences: &[&str],
+    ) -> Result<HashMap<String, Vec<Morpheme>>, Error> {
+        let entries = analysis_cache_entries::table
+            .filter(analysis_cache_entries::configuration_key.eq(configuration_key))
+            .filter(analysis_cache_entries::sentence.eq_any(sentences))
+            .select((
+                analysis_cache_entries::sentence,
+                analysis_cache_entries::morphemes,
+            ))
+            .load::<(String, String)>(database_connection)?;
+
+        // Entries written by an older morpheme layout are treated as missing and re-analyzed.
+        Ok(entries
+            .into_iter()
+            .filter_map(|(sentence, morphemes)| {
+                serde_json::from_str(&morphemes)
+                    .ok()
+                    .map(|morphemes| (sentence, morphemes))
+            })
+            .collect())
+    }
+}
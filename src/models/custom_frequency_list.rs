@@ -0,0 +1,183 @@
+use crate::diesel::prelude::*;
+use crate::frequency_list::normalization::normalize_width;
+use crate::frequency_list::{frequency_list_from_entries, FrequencyList};
+use crate::language::Language;
+use crate::models::user::User;
+use crate::schema::{custom_frequency_list_entries, custom_frequency_lists};
+use chrono::NaiveDateTime;
+use diesel::dsl::any;
+use diesel::result::Error;
+use diesel::{PgConnection, RunQueryDsl};
+use rocket::serde::Serialize;
+
+// Keeps each insert below the 65535 bind parameter limit of Postgres.
+const ENTRY_INSERT_CHUNK_SIZE: usize = 10000;
+
+/// A user's uploaded frequency list for one language.
+#[derive(Queryable, Serialize, Identifiable, PartialEq, Associations, Debug)]
+#[belongs_to(User)]
+#[primary_key(user_id, language)]
+#[table_name = "custom_frequency_lists"]
+pub struct CustomFrequencyList {
+    #[serde(skip_serializing)]
+    pub user_id: i32,
+    pub word_count: i32,
+    pub created_at: NaiveDateTime,
+    pub language: Language,
+}
+
+#[derive(Insertable)]
+#[table_name = "custom_frequency_lists"]
+pub struct NewCustomFrequencyList {
+    pub user_id: i32,
+    pub word_count: i32,
+    pub language: Language,
+}
+
+#[derive(Queryable, Identifiable, PartialEq, Debug)]
+#[table_name = "custom_frequency_list_entries"]
+pub struct CustomFrequencyListEntry {
+    pub id: i32,
+    pub user_id: i32,
+    pub normalized_form: String,
+    pub dictionary_form: String,
+    pub reading: Option<String>,
+    pub frequency: i32,
+    pub language: Language,
+}
+
+#[derive(Insertable)]
+#[table_name = "custom_frequency_list_entries"]
+pub struct NewCustomFrequencyListEntry {
+    pub user_id: i32,
+    pub normalized_form: String,
+    pub dictionary_form: String,
+    pub reading: Option<String>,
+    pub frequency: i32,
+    pub language: Language,
+}
+
+impl CustomFrequencyList {
+    /// Replaces the user's custom frequency list for their language, if any, with the given one.
+    pub fn save(
+        database_connection: &PgConnection,
+        user: &User,
+        frequency_list: &dyn FrequencyList,
+    ) -> Result<Self, Error> {
+        database_connection.transaction(|| {
+            Self::delete(database_connection, user)?;
+
+            let custom_frequency_list = diesel::insert_into(custom_frequency_lists::table)
+                .values(NewCustomFrequencyList {
+                    user_id: user.id,
+                    word_count: frequency_list.len() as i32,
+                    language: user.language,
+                })
+                .get_result::<CustomFrequencyList>(database_connection)?;
+
+            let entries = frequency_list
+                .entries()
+                .into_iter()
+                .map(
+                    |(dictionary_form, reading, frequency)| NewCustomFrequencyListEntry {
+                        user_id: user.id,
+                        normalized_form: normalize_width(&dictionary_form).into_owned(),
+                        dictionary_form,
+                        reading,
+                        frequency: frequency as i32,
+                        language: user.language,
+                    },
+                )
+                .collect::<Vec<NewCustomFrequencyListEntry>>();
+
+            for entries in entries.chunks(ENTRY_INSERT_CHUNK_SIZE) {
+                diesel::insert_into(custom_frequency_list_entries::table)
+                    .values(entries)
+                    .execute(database_connection)?;
+            }
+
+            Ok(custom_frequency_list)
+        })
+    }
+
+    pub fn find(
+        database_connection: &PgConnection,
+        user_id: i32,
+        language: Language,
+    ) -> Result<Option<Self>, Error> {
+        custom_frequency_lists::table
+            .find((user_id, language))
+            .first::<CustomFrequencyList>(database_connection)
+            .optional()
+    }
+
+    /// The user's custom frequency list for the language restricted to the given dictionary forms,
+    /// if they uploaded one.
+    pub fn find_frequency_list_for_words(
+        database_connection: &PgConnection,
+        user_id: i32,
+        language: Language,
+        dictionary_forms: &[String],
+    ) -> Result<Option<Box<dyn FrequencyList>>, Error> {
+        Self::find(database_connection, user_id, language)?
+            .map(|custom_frequency_list| {
+                custom_frequency_list
+                    .get_frequency_list_for_words(database_connection, dictionary_forms)
+            })
+            .transpose()
+    }
+
+    /// Returns whether the user had a custom frequency list for their language.
+    pub fn delete(database_connection: &PgConnection, user: &User) -> Result<bool, Error> {
+        database_connection.transaction(|| {
+            diesel::delete(
+                custom_frequency_list_entries::table
+                    .filter(custom_frequency_list_entries::user_id.eq(user.id))
+                    .filter(custom_frequency_list_entries::language.eq(user.language)),
+            )
+            .execute(database_connection)?;
+
+            let deleted_count = diesel::delete(
+                CustomFrequencyList::belonging_to(user)
+                    .filter(custom_frequency_lists::language.eq(user.language)),
+            )
+            .execute(database_connection)?;
+
+            Ok(deleted_count > 0)
+        })
+    }
+
+    /// Loads only the entries for the given dictionary forms, which is all a request needs to rank
+    /// its words without reading the whole list.
+    pub fn get_frequency_list_for_words(
+        &self,
+        database_connection: &PgConnection,
+        dictionary_forms: &[String],
+    ) -> Result<Box<dyn FrequencyList>, Error> {
+        let normalized_forms = dictionary_forms
+            .iter()
+            .map(|dictionary_form| normalize_width(dictionary_form).into_owned())
+            .collect::<Vec<String>>();
+
+        let entries: Vec<CustomFrequencyListEntry> = custom_frequency_list_entries::table
+            .filter(custom_frequency_list_entries::user_id.eq(self.user_id))
+            .filter(custom_frequency_list_entries::language.eq(self.language))
+            .filter(custom_frequency_list_entries::normalized_form.eq(any(normalized_forms)))
+            .get_results(database_connection)?;
+
+        Ok(frequency_list_from_entries(
+            self.language,
+            entries
+                .into_iter()
+                .map(|entry| {
+                    (
+                        entry.dictionary_form,
+                        entry.reading,
+                        entry.frequency as usize,
+                    )
+                })
+                .collect(),
+            self.word_count as usize,
+        ))
+    }
+}
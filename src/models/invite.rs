@@ -0,0 +1,81 @@
+use crate::diesel::prelude::*;
+use crate::models::user::User;
+use crate::schema::invites;
+use chrono::{NaiveDateTime, Utc};
+use diesel::result::Error;
+use diesel::{PgConnection, RunQueryDsl};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use rocket::serde::Serialize;
+
+const INVITE_CODE_LENGTH: usize = 16;
+
+/// A code letting others register while registration is invite-only.
+#[derive(Queryable, Serialize, Identifiable, PartialEq, Associations, Debug)]
+#[belongs_to(User, foreign_key = "created_by")]
+pub struct Invite {
+    pub id: i32,
+    pub code: String,
+    pub created_by: i32,
+    pub remaining_uses: i32,
+    pub expires_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[table_name = "invites"]
+pub struct NewInvite {
+    pub code: String,
+    pub created_by: i32,
+    pub remaining_uses: i32,
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+impl Invite {
+    pub fn new(
+        database_connection: &PgConnection,
+        user: &User,
+        uses: i32,
+        expires_at: Option<NaiveDateTime>,
+    ) -> Result<Self, Error> {
+        let code = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(INVITE_CODE_LENGTH)
+            .map(char::from)
+            .collect();
+
+        diesel::insert_into(invites::table)
+            .values(NewInvite {
+                code,
+                created_by: user.id,
+                remaining_uses: uses,
+                expires_at,
+            })
+            .get_result::<Invite>(database_connection)
+    }
+
+    /// Uses up one use of an invite, unless it has none left or has expired. Returns whether the
+    /// invite could be used.
+    pub fn redeem(database_connection: &PgConnection, code: &str) -> Result<bool, Error> {
+        let updated_invites = diesel::update(
+            invites::table
+                .filter(invites::code.eq(code))
+                .filter(invites::remaining_uses.gt(0))
+                .filter(
+                    invites::expires_at
+                        .is_null()
+                        .or(invites::expires_at.gt(Utc::now().naive_utc())),
+                ),
+        )
+        .set(invites::remaining_uses.eq(invites::remaining_uses - 1))
+        .execute(database_connection)?;
+
+        Ok(updated_invites > 0)
+    }
+
+    pub fn delete(&self, database_connection: &PgConnection) -> Result<(), Error> {
+        diesel::delete(self).execute(database_connection)?;
+
+        Ok(())
+    }
+}
@@ -1,8 +1,11 @@
 use crate::diesel::prelude::*;
-use crate::frequency_list::JpFrequencyList;
+use crate::frequency_list::bands::FrequencyBands;
+use crate::frequency_list::jlpt::JlptList;
+use crate::frequency_list::FrequencyList;
 use crate::models::sentence::Sentence;
 use crate::models::user::{User, UserSentenceEntry};
 use crate::models::word::Word;
+use crate::models::word_kanji::WordKanji;
 use crate::schema::mining_batches;
 use crate::schema::words::dsl::words as dsl_words;
 use chrono::NaiveDateTime;
@@ -38,15 +41,37 @@ impl MiningBatch {
     pub fn get_sentences(
         &self,
         database_connection: &PgConnection,
-        frequency_list: &JpFrequencyList,
+        user: &User,
+        frequency_list: &dyn FrequencyList,
+        frequency_bands: &FrequencyBands,
+        jlpt_list: &JlptList,
     ) -> Result<Vec<UserSentenceEntry>, Error> {
         let rows: Vec<(Sentence, Word)> = Sentence::belonging_to(self)
             .inner_join(dsl_words)
             .load(database_connection)?;
 
+        let dictionary_forms = rows
+            .iter()
+            .map(|(_, word)| word.dictionary_form.clone())
+            .collect::<Vec<String>>();
+        let custom_frequency_list =
+            user.get_custom_frequency_list(database_connection, &dictionary_forms)?;
+        let frequency_list = custom_frequency_list.as_deref().unwrap_or(frequency_list);
+        let known_kanji = WordKanji::get_known_kanji(database_connection, user)?;
+
         let sentences = rows
             .into_iter()
-            .map(|(sentence, word)| UserSentenceEntry::new(&word, &sentence, frequency_list))
+            .map(|(sentence, word)| {
+                UserSentenceEntry::new(
+                    &word,
+                    &sentence,
+                    frequency_list,
+                    frequency_bands,
+                    jlpt_list,
+                    &known_kanji,
+                    None,
+                )
+            })
             .collect();
 
         Ok(sentences)
@@ -1,4 +1,10 @@
+pub mod analysis_cache_entry;
+pub mod custom_frequency_list;
+pub mod invite;
 pub mod mining_batch;
+pub mod personal_access_token;
 pub mod sentence;
+pub mod session;
 pub mod user;
 pub mod word;
+pub mod word_kanji;
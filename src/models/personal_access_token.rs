@@ -0,0 +1,135 @@
+use crate::diesel::prelude::*;
+use crate::jwt::TokenError;
+use crate::models::user::User;
+use crate::schema::personal_access_tokens;
+use crate::scope::Scope;
+use chrono::{NaiveDateTime, Utc};
+use diesel::result::Error;
+use diesel::{PgConnection, RunQueryDsl};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use rocket::serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// Prefix telling personal access tokens apart from JWTs in the `Authorization` header.
+pub const PERSONAL_ACCESS_TOKEN_PREFIX: &str = "sbp_";
+const PERSONAL_ACCESS_TOKEN_LENGTH: usize = 40;
+
+/// A long-lived token for scripts and extensions, limited to its scopes. Only a hash of the
+/// token is stored, so it can't be shown again after it was created.
+#[derive(Queryable, Serialize, Identifiable, PartialEq, Associations, Debug)]
+#[belongs_to(User)]
+pub struct PersonalAccessToken {
+    pub id: i32,
+    #[serde(skip_serializing)]
+    pub user_id: i32,
+    pub name: String,
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+    pub scopes: Vec<Scope>,
+    pub expires_at: Option<NaiveDateTime>,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[table_name = "personal_access_tokens"]
+pub struct NewPersonalAccessToken {
+    pub user_id: i32,
+    pub name: String,
+    pub token_hash: String,
+    pub scopes: Vec<Scope>,
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+impl PersonalAccessToken {
+    /// Creates a token, returning it along with the token itself.
+    pub fn new(
+        database_connection: &PgConnection,
+        user: &User,
+        name: String,
+        scopes: Vec<Scope>,
+        expires_at: Option<NaiveDateTime>,
+    ) -> Result<(Self, String), Error> {
+        let token = format!(
+            "{}{}",
+            PERSONAL_ACCESS_TOKEN_PREFIX,
+            rand::thread_rng()
+                .sample_iter(&Alphanumeric)
+                .take(PERSONAL_ACCESS_TOKEN_LENGTH)
+                .map(char::from)
+                .collect::<String>()
+        );
+
+        let personal_access_token = diesel::insert_into(personal_access_tokens::table)
+            .values(NewPersonalAccessToken {
+                user_id: user.id,
+                name,
+                token_hash: hash_token(&token),
+                scopes,
+                expires_at,
+            })
+            .get_result::<PersonalAccessToken>(database_connection)?;
+
+        Ok((personal_access_token, token))
+    }
+
+    pub fn is_personal_access_token(token: &str) -> bool {
+        token.starts_with(PERSONAL_ACCESS_TOKEN_PREFIX)
+    }
+
+    /// Finds the user of a token, given it has not expired and grants `required_scope`.
+    pub fn authenticate(
+        database_connection: &PgConnection,
+        token: &str,
+        required_scope: Option<Scope>,
+    ) -> Result<User, TokenError> {
+        let personal_access_token = personal_access_tokens::table
+            .filter(personal_access_tokens::token_hash.eq(hash_token(token)))
+            .get_result::<PersonalAccessToken>(database_connection)
+            .map_err(|error| match error {
+                Error::NotFound => TokenError::Revoked,
+                _ => TokenError::DatabaseError,
+            })?;
+
+        if let Some(expires_at) = personal_access_token.expires_at {
+            if expires_at <= Utc::now().naive_utc() {
+                return Err(TokenError::Expired);
+            }
+        }
+
+        match required_scope {
+            Some(scope) if personal_access_token.scopes.contains(&scope) => {}
+            _ => return Err(TokenError::InsufficientScope),
+        }
+
+        let user = User::find_by_id(database_connection, personal_access_token.user_id)
+            .ok_or(TokenError::InvalidSubject)?;
+
+        diesel::update(&personal_access_token)
+            .set(personal_access_tokens::last_used_at.eq(Utc::now().naive_utc()))
+            .execute(database_connection)
+            .map_err(|_| TokenError::DatabaseError)?;
+
+        Ok(user)
+    }
+
+    pub fn delete(&self, database_connection: &PgConnection) -> Result<(), Error> {
+        diesel::delete(self).execute(database_connection)?;
+
+        Ok(())
+    }
+
+    pub fn delete_all_for_user(
+        database_connection: &PgConnection,
+        user: &User,
+    ) -> Result<(), Error> {
+        diesel::delete(PersonalAccessToken::belonging_to(user)).execute(database_connection)?;
+
+        Ok(())
+    }
+}
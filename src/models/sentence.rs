@@ -1,3 +1,4 @@
+use crate::language::Language;
 use crate::models::mining_batch::MiningBatch;
 use crate::models::user::User;
 use crate::models::word::Word;
@@ -20,6 +21,7 @@ pub struct Sentence {
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
     pub mining_batch_id: Option<i32>,
+    pub language: Language,
 }
 
 #[derive(Insertable)]
@@ -28,6 +30,7 @@ pub struct NewSentence {
     pub user_id: i32,
     pub word_id: i32,
     pub sentence: String,
+    pub language: Language,
 }
 
 impl Sentence {
@@ -42,6 +45,7 @@ impl Sentence {
                 user_id: user.id,
                 word_id: word.id,
                 sentence: sentence.to_string(),
+                language: word.language,
             })
             .get_result::<Sentence>(database_connection)
     }
@@ -0,0 +1,129 @@
+use crate::diesel::prelude::*;
+use crate::models::user::User;
+use crate::schema::sessions;
+use chrono::{NaiveDateTime, Utc};
+use diesel::result::Error;
+use diesel::{PgConnection, RunQueryDsl};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use rocket::request::{FromRequest, Outcome, Request};
+use rocket::serde::Serialize;
+use std::convert::Infallible;
+
+const REFRESH_TOKEN_ID_LENGTH: usize = 32;
+
+/// A device the user logged in from. Its refresh tokens stop working once it is deleted.
+///
+/// Only the latest refresh token of a session, identified by `refresh_token_id`, can be used.
+#[derive(Queryable, Serialize, Identifiable, PartialEq, Associations, Debug)]
+#[belongs_to(User)]
+pub struct Session {
+    pub id: i32,
+    #[serde(skip_serializing)]
+    pub user_id: i32,
+    pub device_name: Option<String>,
+    pub user_agent: Option<String>,
+    pub last_ip: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub last_used_at: NaiveDateTime,
+    /// Sessions opened before refresh tokens were rotated have none.
+    #[serde(skip_serializing)]
+    pub refresh_token_id: Option<String>,
+}
+
+#[derive(Insertable)]
+#[table_name = "sessions"]
+pub struct NewSession {
+    pub user_id: i32,
+    pub device_name: Option<String>,
+    pub user_agent: Option<String>,
+    pub last_ip: Option<String>,
+    pub refresh_token_id: Option<String>,
+}
+
+fn generate_refresh_token_id() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(REFRESH_TOKEN_ID_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+/// User agent and address of the client of a request, recorded on its session.
+pub struct SessionClient {
+    pub user_agent: Option<String>,
+    pub ip: Option<String>,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for SessionClient {
+    type Error = Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Outcome::Success(SessionClient {
+            user_agent: request
+                .headers()
+                .get_one("User-Agent")
+                .map(|user_agent| user_agent.to_string()),
+            ip: request.client_ip().map(|ip| ip.to_string()),
+        })
+    }
+}
+
+impl Session {
+    pub fn new(
+        database_connection: &PgConnection,
+        user: &User,
+        device_name: Option<String>,
+        client: &SessionClient,
+    ) -> Result<Self, Error> {
+        diesel::insert_into(sessions::table)
+            .values(NewSession {
+                user_id: user.id,
+                device_name,
+                user_agent: client.user_agent.clone(),
+                last_ip: client.ip.clone(),
+                refresh_token_id: Some(generate_refresh_token_id()),
+            })
+            .get_result::<Session>(database_connection)
+    }
+
+    /// Replaces the refresh token of the session, recording that it was just used by the given
+    /// client. Returns `None` without changing anything if `refresh_token_id` does not identify
+    /// the latest refresh token, e.g. because it was already rotated.
+    pub fn rotate(
+        &self,
+        database_connection: &PgConnection,
+        refresh_token_id: Option<&str>,
+        client: &SessionClient,
+    ) -> Result<Option<Self>, Error> {
+        diesel::update(
+            sessions::table
+                .filter(sessions::id.eq(self.id))
+                .filter(sessions::refresh_token_id.is_not_distinct_from(refresh_token_id)),
+        )
+        .set((
+            sessions::refresh_token_id.eq(generate_refresh_token_id()),
+            sessions::last_used_at.eq(Utc::now().naive_utc()),
+            sessions::last_ip.eq(client.ip.as_ref().or(self.last_ip.as_ref())),
+            sessions::user_agent.eq(client.user_agent.as_ref().or(self.user_agent.as_ref())),
+        ))
+        .get_result::<Session>(database_connection)
+        .optional()
+    }
+
+    pub fn delete(&self, database_connection: &PgConnection) -> Result<(), Error> {
+        diesel::delete(self).execute(database_connection)?;
+
+        Ok(())
+    }
+
+    pub fn delete_all_for_user(
+        database_connection: &PgConnection,
+        user: &User,
+    ) -> Result<(), Error> {
+        diesel::delete(Session::belonging_to(user)).execute(database_connection)?;
+
+        Ok(())
+    }
+}
@@ -1,22 +1,43 @@
 use crate::database::Pool;
-use crate::frequency_list::JpFrequencyList;
+use crate::frequency_list::bands::FrequencyBands;
+use crate::frequency_list::jlpt::{JlptLevel, JlptList};
+use crate::frequency_list::{FrequencyList, FrequencyMatch};
 use crate::helpers::get_maximum_pending_sentences;
 use crate::jwt::{extract_access_token_from_header, validate_token, TokenError, TokenType};
+use crate::kanji::extract_kanji;
+use crate::language::Language;
+use crate::models::custom_frequency_list::CustomFrequencyList;
+use crate::models::invite::Invite;
 use crate::models::mining_batch::MiningBatch;
+use crate::models::personal_access_token::PersonalAccessToken;
 use crate::models::sentence::Sentence;
+use crate::models::session::Session;
 use crate::models::word::Word;
+use crate::models::word_kanji::WordKanji;
+use crate::schema::invites::{created_at as schema_invites_created_at, id as schema_invites_id};
 use crate::schema::mining_batches::{
     created_at as schema_mining_batches_created_at, id as schema_mining_batches_id,
 };
+use crate::schema::personal_access_tokens::{
+    created_at as schema_personal_access_tokens_created_at, id as schema_personal_access_tokens_id,
+};
 use crate::schema::sentences::dsl::sentences as dsl_sentences;
 use crate::schema::sentences::{
     id as schema_sentences_id, is_pending as schema_sentences_is_pending,
-    mining_batch_id as schema_sentences_mining_batch_id, user_id as schema_sentences_user_id,
+    language as schema_sentences_language, mining_batch_id as schema_sentences_mining_batch_id,
+    sentence as schema_sentences_sentence, user_id as schema_sentences_user_id,
+};
+use crate::schema::sessions::{
+    id as schema_sessions_id, last_used_at as schema_sessions_last_used_at,
 };
 use crate::schema::users;
 use crate::schema::words::dsl::words as dsl_words;
-use crate::schema::words::{id as schema_words_id, is_mined as schema_words_is_mined};
-use bcrypt::{hash, verify, DEFAULT_COST};
+use crate::schema::words::{
+    dictionary_form as schema_words_dictionary_form, id as schema_words_id,
+    is_mined as schema_words_is_mined, language as schema_words_language,
+};
+use crate::scope::Scope;
+use bcrypt::{hash, verify, BcryptError, DEFAULT_COST};
 use chrono::NaiveDateTime;
 use diesel;
 use diesel::dsl::any;
@@ -29,7 +50,8 @@ use rocket::outcome::try_outcome;
 use rocket::request::{FromRequest, Outcome, Request};
 use rocket::serde::{Deserialize, Serialize};
 use rocket::State;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
 
 #[derive(Queryable, Serialize, Identifiable, AsChangeset, PartialEq)]
 pub struct User {
@@ -42,6 +64,8 @@ pub struct User {
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
     pub token_generation: i32,
+    pub frequency_list: Option<String>,
+    pub language: Language,
 }
 
 #[derive(Insertable)]
@@ -60,27 +84,83 @@ pub struct UserSentenceEntry {
     pub reading: String,
     pub mining_frequency: i32,
     pub dictionary_frequency: usize,
+    pub dictionary_frequency_match: FrequencyMatch,
+    pub dictionary_frequency_band: String,
+    pub jlpt_level: Option<JlptLevel>,
+    /// Kanji of the sentence missing from every mined word.
+    pub new_kanji: Vec<String>,
+    pub has_new_kanji: bool,
+    /// Difficulty score of the sentence, computed when pending sentences are sorted by it.
+    pub difficulty: Option<f64>,
 }
 
 impl UserSentenceEntry {
-    pub fn new(word: &Word, sentence: &Sentence, frequency_list: &JpFrequencyList) -> Self {
+    pub fn new(
+        word: &Word,
+        sentence: &Sentence,
+        frequency_list: &dyn FrequencyList,
+        frequency_bands: &FrequencyBands,
+        jlpt_list: &JlptList,
+        known_kanji: &HashSet<String>,
+        difficulty: Option<f64>,
+    ) -> Self {
+        let frequency_lookup = frequency_list.lookup(&word.dictionary_form, &word.reading);
+        let new_kanji = extract_kanji(&sentence.sentence)
+            .into_iter()
+            .filter(|kanji| !known_kanji.contains(kanji))
+            .collect::<Vec<String>>();
+
         UserSentenceEntry {
             sentence_id: sentence.id,
             sentence: sentence.sentence.clone(),
             dictionary_form: word.dictionary_form.clone(),
             reading: word.reading.clone(),
             mining_frequency: word.frequency,
-            dictionary_frequency: frequency_list
-                .get_frequency(&word.dictionary_form, &word.reading),
+            dictionary_frequency: frequency_lookup.frequency,
+            dictionary_frequency_match: frequency_lookup.match_type,
+            dictionary_frequency_band: frequency_bands.get_band(frequency_lookup).to_string(),
+            jlpt_level: jlpt_list.lookup(word.language, &word.dictionary_form, Some(&word.reading)),
+            has_new_kanji: !new_kanji.is_empty(),
+            new_kanji,
+            difficulty,
         }
     }
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Default)]
+pub enum PendingSentenceOrder {
+    /// From the most mined word, then from the most frequent word.
+    #[default]
+    Mining,
+    /// From the easiest sentence to the hardest.
+    Difficulty,
+}
+
+impl FromStr for PendingSentenceOrder {
+    type Err = String;
+
+    fn from_str(order: &str) -> Result<Self, Self::Err> {
+        match order {
+            "mining" => Ok(PendingSentenceOrder::Mining),
+            "difficulty" => Ok(PendingSentenceOrder::Difficulty),
+            _ => Err(format!("\"{}\" is not a pending sentence order", order)),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct PendingSentencesOptions {
+    /// Keeps only the sentences whose word is at this level.
+    pub jlpt_level: Option<JlptLevel>,
+    pub order: PendingSentenceOrder,
+}
+
 #[derive(Debug)]
 pub enum UserRegistrationError {
     DuplicateEmail,
     DuplicateUsername,
     FailedToHash,
+    InvalidInviteCode,
 }
 
 impl From<Error> for UserRegistrationError {
@@ -97,6 +177,27 @@ impl From<Error> for UserRegistrationError {
     }
 }
 
+#[derive(Debug)]
+pub enum ChangePasswordError {
+    FailedToHash,
+    DatabaseError(Error),
+}
+
+impl From<Error> for ChangePasswordError {
+    fn from(err: Error) -> ChangePasswordError {
+        ChangePasswordError::DatabaseError(err)
+    }
+}
+
+fn hash_password(password: String) -> Result<String, BcryptError> {
+    let hashing_cost = match std::env::var("HASHING_COST") {
+        Ok(cost) => cost.parse::<u32>().unwrap_or(DEFAULT_COST),
+        Err(_) => DEFAULT_COST,
+    };
+
+    hash(password, hashing_cost)
+}
+
 pub enum CommitSentencesError {
     DatabaseError(Error),
     InvalidSentencesProvided,
@@ -107,29 +208,45 @@ impl<'r> FromRequest<'r> for User {
     type Error = TokenError;
 
     async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
-        let authorization_header = request.headers().get("Authorization").collect::<String>();
-
-        let token = match extract_access_token_from_header(authorization_header) {
-            Some(token) => token,
-            None => return TokenError::NoToken.outcome(request),
-        };
+        authenticate_request(request, None).await
+    }
+}
 
-        let pool =
-            try_outcome!(request
-                .guard::<&State<Pool>>()
-                .await
-                .map_failure(|(status, _)| {
-                    request.local_cache(|| TokenError::None);
-                    (status, TokenError::None)
-                }));
-
-        match pool.get() {
-            Ok(connection) => match validate_token(token, TokenType::Access, &connection) {
-                Ok(user) => Outcome::Success(user),
-                Err(error) => error.outcome(request),
-            },
-            Err(_) => TokenError::None.outcome(request),
-        }
+/// Authenticates the user of a request by its JWT, or by a personal access token granting
+/// `required_scope`. Without a required scope, only JWTs are accepted.
+pub async fn authenticate_request(
+    request: &Request<'_>,
+    required_scope: Option<Scope>,
+) -> Outcome<User, TokenError> {
+    let authorization_header = request.headers().get("Authorization").collect::<String>();
+
+    let token = match extract_access_token_from_header(authorization_header) {
+        Some(token) => token,
+        None => return TokenError::NoToken.outcome(request),
+    };
+
+    let pool = try_outcome!(request
+        .guard::<&State<Pool>>()
+        .await
+        .map_failure(|(status, _)| {
+            request.local_cache(|| TokenError::None);
+            (status, TokenError::None)
+        }));
+
+    let connection = match pool.get() {
+        Ok(connection) => connection,
+        Err(_) => return TokenError::None.outcome(request),
+    };
+
+    let validation_result = if PersonalAccessToken::is_personal_access_token(&token) {
+        PersonalAccessToken::authenticate(&connection, &token, required_scope)
+    } else {
+        validate_token(token, TokenType::Access, &connection)
+    };
+
+    match validation_result {
+        Ok(user) => Outcome::Success(user),
+        Err(error) => error.outcome(request),
     }
 }
 
@@ -151,28 +268,27 @@ impl User {
             .get_result::<User>(database_connection)
             .ok()?;
 
-        if verify(password, &user.hash).ok()? {
+        if user.verify_password(&password) {
             Some(user)
         } else {
             None
         }
     }
 
+    pub fn verify_password(&self, password: &str) -> bool {
+        verify(password, &self.hash).unwrap_or(false)
+    }
+
     pub fn register(
         database_connection: &PgConnection,
         username: String,
         email: String,
         password: String,
     ) -> Result<User, UserRegistrationError> {
-        let hashing_cost = match std::env::var("HASHING_COST") {
-            Ok(cost) => cost.parse::<u32>().unwrap_or(DEFAULT_COST),
-            Err(_) => DEFAULT_COST,
-        };
-
         let new_user = NewUser {
             username,
             email,
-            hash: hash(password, hashing_cost).map_err(|_| UserRegistrationError::FailedToHash)?,
+            hash: hash_password(password).map_err(|_| UserRegistrationError::FailedToHash)?,
         };
 
         diesel::insert_into(users::table)
@@ -181,6 +297,24 @@ impl User {
             .map_err(Into::into)
     }
 
+    /// Registers a user with one use of an invite, leaving the invite untouched when the
+    /// registration fails.
+    pub fn register_with_invite(
+        database_connection: &PgConnection,
+        username: String,
+        email: String,
+        password: String,
+        invite_code: &str,
+    ) -> Result<User, UserRegistrationError> {
+        database_connection.transaction(|| {
+            if !Invite::redeem(database_connection, invite_code)? {
+                return Err(UserRegistrationError::InvalidInviteCode);
+            }
+
+            Self::register(database_connection, username, email, password)
+        })
+    }
+
     pub fn increment_token_generation(
         &mut self,
         database_connection: &PgConnection,
@@ -191,6 +325,42 @@ impl User {
         Ok(self.token_generation)
     }
 
+    /// Replaces the password and revokes every token issued until now.
+    pub fn change_password(
+        &mut self,
+        database_connection: &PgConnection,
+        password: String,
+    ) -> Result<i32, ChangePasswordError> {
+        self.hash = hash_password(password).map_err(|_| ChangePasswordError::FailedToHash)?;
+        self.token_generation += 1;
+        self.save_changes::<User>(database_connection)
+            .map_err(ChangePasswordError::DatabaseError)?;
+
+        Ok(self.token_generation)
+    }
+
+    pub fn set_frequency_list(
+        &mut self,
+        database_connection: &PgConnection,
+        frequency_list: String,
+    ) -> Result<(), Error> {
+        self.frequency_list = Some(frequency_list);
+        self.save_changes::<User>(database_connection)?;
+
+        Ok(())
+    }
+
+    pub fn set_language(
+        &mut self,
+        database_connection: &PgConnection,
+        language: Language,
+    ) -> Result<(), Error> {
+        self.language = language;
+        self.save_changes::<User>(database_connection)?;
+
+        Ok(())
+    }
+
     pub fn is_pending_sentence_limit_reached(
         &self,
         database_connection: &PgConnection,
@@ -203,23 +373,82 @@ impl User {
         Ok(pending_sentences >= get_maximum_pending_sentences() as i64)
     }
 
+    /// Texts of the pending sentences in the user's language, e.g. to analyze them before
+    /// scoring their difficulty.
+    pub fn get_pending_sentence_texts(
+        &self,
+        database_connection: &PgConnection,
+    ) -> Result<Vec<String>, Error> {
+        Sentence::belonging_to(self)
+            .filter(schema_sentences_is_pending.eq(true))
+            .filter(schema_sentences_language.eq(self.language))
+            .select(schema_sentences_sentence)
+            .load(database_connection)
+    }
+
+    /// Pending sentences, grouped by mining frequency from the most mined word and then by
+    /// dictionary frequency, or from the easiest to the hardest. `difficulties` holds the
+    /// difficulty of each sentence text.
     pub fn get_pending_sentences(
         &self,
         database_connection: &PgConnection,
-        frequency_list: &JpFrequencyList,
+        frequency_list: &dyn FrequencyList,
+        frequency_bands: &FrequencyBands,
+        jlpt_list: &JlptList,
+        difficulties: &HashMap<String, f64>,
+        options: &PendingSentencesOptions,
     ) -> Result<Vec<UserSentenceEntry>, Error> {
         let rows: Vec<(Sentence, Word)> = Sentence::belonging_to(self)
             .filter(schema_sentences_is_pending.eq(true))
+            .filter(schema_sentences_language.eq(self.language))
             .inner_join(dsl_words)
             .load(database_connection)?;
 
+        let dictionary_forms = rows
+            .iter()
+            .map(|(_, word)| word.dictionary_form.clone())
+            .collect::<Vec<String>>();
+        let custom_frequency_list =
+            self.get_custom_frequency_list(database_connection, &dictionary_forms)?;
+        let frequency_list = custom_frequency_list.as_deref().unwrap_or(frequency_list);
+        let known_kanji = WordKanji::get_known_kanji(database_connection, self)?;
+
         let mut frequency_groups: HashMap<i32, Vec<UserSentenceEntry>> = HashMap::new();
 
         for (sentence, word) in rows {
+            let user_sentence_entry = UserSentenceEntry::new(
+                &word,
+                &sentence,
+                frequency_list,
+                frequency_bands,
+                jlpt_list,
+                &known_kanji,
+                difficulties.get(&sentence.sentence).copied(),
+            );
+
+            if options.jlpt_level.is_some() && user_sentence_entry.jlpt_level != options.jlpt_level
+            {
+                continue;
+            }
+
             frequency_groups
                 .entry(word.frequency)
                 .or_default()
-                .push(UserSentenceEntry::new(&word, &sentence, frequency_list));
+                .push(user_sentence_entry);
+        }
+
+        if options.order == PendingSentenceOrder::Difficulty {
+            // Sentences without a score, only added while the scores were computed, come last.
+            return Ok(frequency_groups
+                .into_values()
+                .flatten()
+                .sorted_by(|lhs, rhs| {
+                    lhs.difficulty
+                        .unwrap_or(f64::INFINITY)
+                        .total_cmp(&rhs.difficulty.unwrap_or(f64::INFINITY))
+                        .then(lhs.dictionary_frequency.cmp(&rhs.dictionary_frequency))
+                })
+                .collect::<Vec<UserSentenceEntry>>());
         }
 
         Ok(frequency_groups
@@ -247,6 +476,7 @@ impl User {
     ) -> Result<MiningBatch, CommitSentencesError> {
         let rows: Vec<(Sentence, Word)> = Sentence::belonging_to(self)
             .filter(schema_sentences_is_pending.eq(true))
+            .filter(schema_sentences_language.eq(self.language))
             .filter(schema_sentences_id.eq(any(sentence_ids)))
             .inner_join(dsl_words)
             .load(database_connection)
@@ -266,6 +496,7 @@ impl User {
 
         diesel::update(dsl_sentences.filter(schema_sentences_user_id.eq(self.id)))
             .filter(schema_sentences_is_pending.eq(true))
+            .filter(schema_sentences_language.eq(self.language))
             .set(schema_sentences_is_pending.eq(false))
             .execute(database_connection)
             .map_err(CommitSentencesError::DatabaseError)?;
@@ -308,6 +539,66 @@ impl User {
             .ok()
     }
 
+    pub fn get_invite_by_id(
+        &self,
+        database_connection: &PgConnection,
+        invite_id: i32,
+    ) -> Option<Invite> {
+        Invite::belonging_to(self)
+            .filter(schema_invites_id.eq(invite_id))
+            .get_result(database_connection)
+            .ok()
+    }
+
+    pub fn get_all_invites(
+        &self,
+        database_connection: &PgConnection,
+    ) -> Result<Vec<Invite>, Error> {
+        Invite::belonging_to(self)
+            .order(schema_invites_created_at.desc())
+            .get_results(database_connection)
+    }
+
+    pub fn get_session_by_id(
+        &self,
+        database_connection: &PgConnection,
+        session_id: i32,
+    ) -> Option<Session> {
+        Session::belonging_to(self)
+            .filter(schema_sessions_id.eq(session_id))
+            .get_result(database_connection)
+            .ok()
+    }
+
+    pub fn get_all_sessions(
+        &self,
+        database_connection: &PgConnection,
+    ) -> Result<Vec<Session>, Error> {
+        Session::belonging_to(self)
+            .order(schema_sessions_last_used_at.desc())
+            .get_results(database_connection)
+    }
+
+    pub fn get_personal_access_token_by_id(
+        &self,
+        database_connection: &PgConnection,
+        personal_access_token_id: i32,
+    ) -> Option<PersonalAccessToken> {
+        PersonalAccessToken::belonging_to(self)
+            .filter(schema_personal_access_tokens_id.eq(personal_access_token_id))
+            .get_result(database_connection)
+            .ok()
+    }
+
+    pub fn get_all_personal_access_tokens(
+        &self,
+        database_connection: &PgConnection,
+    ) -> Result<Vec<PersonalAccessToken>, Error> {
+        PersonalAccessToken::belonging_to(self)
+            .order(schema_personal_access_tokens_created_at.desc())
+            .get_results(database_connection)
+    }
+
     pub fn get_all_mining_batches(
         &self,
         database_connection: &PgConnection,
@@ -318,4 +609,35 @@ impl User {
 
         Ok(mining_batches)
     }
+
+    /// The user's uploaded frequency list restricted to the given words, which takes precedence
+    /// over the selected shared list.
+    pub fn get_custom_frequency_list(
+        &self,
+        database_connection: &PgConnection,
+        dictionary_forms: &[String],
+    ) -> Result<Option<Box<dyn FrequencyList>>, Error> {
+        CustomFrequencyList::find_frequency_list_for_words(
+            database_connection,
+            self.id,
+            self.language,
+            dictionary_forms,
+        )
+    }
+
+    pub fn get_words_by_dictionary_forms(
+        &self,
+        database_connection: &PgConnection,
+        dictionary_forms: &[String],
+    ) -> Result<HashMap<(String, String), Word>, Error> {
+        let words: Vec<Word> = Word::belonging_to(self)
+            .filter(schema_words_language.eq(self.language))
+            .filter(schema_words_dictionary_form.eq(any(dictionary_forms)))
+            .get_results(database_connection)?;
+
+        Ok(words
+            .into_iter()
+            .map(|word| ((word.dictionary_form.clone(), word.reading.clone()), word))
+            .collect())
+    }
 }
@@ -1,14 +1,18 @@
 use crate::diesel::ExpressionMethods;
 use crate::diesel::QueryDsl;
+use crate::language::Language;
 use crate::models::user::User;
+use crate::models::word_kanji::WordKanji;
 use crate::schema::words;
 use crate::schema::words::{
-    dictionary_form as schema_words_dictionary_form, reading as schema_words_reading,
+    dictionary_form as schema_words_dictionary_form, language as schema_words_language,
+    reading as schema_words_reading,
 };
 use chrono::NaiveDateTime;
 use diesel::pg::PgConnection;
 use diesel::result::Error;
 use diesel::BelongingToDsl;
+use diesel::Connection;
 use diesel::RunQueryDsl;
 use diesel::SaveChangesDsl;
 use rocket::serde::Serialize;
@@ -24,6 +28,7 @@ pub struct Word {
     pub is_mined: bool,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    pub language: Language,
 }
 
 #[derive(Insertable)]
@@ -32,6 +37,7 @@ pub struct NewWord {
     pub user_id: i32,
     pub dictionary_form: String,
     pub reading: String,
+    pub language: Language,
 }
 
 impl Word {
@@ -42,6 +48,7 @@ impl Word {
         reading: &str,
     ) -> Result<Word, Error> {
         let potential_word: Result<Word, Error> = Word::belonging_to(user)
+            .filter(schema_words_language.eq(user.language))
             .filter(schema_words_dictionary_form.eq(dictionary_form))
             .filter(schema_words_reading.eq(reading))
             .first(database_connection);
@@ -52,13 +59,20 @@ impl Word {
                 found_word.is_mined = false;
                 found_word.save_changes::<Word>(database_connection)
             }
-            Err(_) => diesel::insert_into(words::table)
-                .values(NewWord {
-                    user_id: user.id,
-                    dictionary_form: dictionary_form.to_string(),
-                    reading: reading.to_string(),
-                })
-                .get_result::<Word>(database_connection),
+            Err(_) => database_connection.transaction(|| {
+                let word = diesel::insert_into(words::table)
+                    .values(NewWord {
+                        user_id: user.id,
+                        dictionary_form: dictionary_form.to_string(),
+                        reading: reading.to_string(),
+                        language: user.language,
+                    })
+                    .get_result::<Word>(database_connection)?;
+
+                WordKanji::insert_for_word(database_connection, &word)?;
+
+                Ok(word)
+            }),
         }
     }
 }
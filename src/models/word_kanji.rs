@@ -0,0 +1,154 @@
+use crate::diesel::prelude::*;
+use crate::kanji::extract_kanji;
+use crate::models::user::User;
+use crate::models::word::Word;
+use crate::schema::{word_kanji, words};
+use chrono::NaiveDateTime;
+use diesel::result::Error;
+use diesel::{PgConnection, RunQueryDsl};
+use rocket::serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+
+/// A kanji of the dictionary form of one of a user's words.
+#[derive(Queryable, Identifiable, PartialEq, Associations, Debug)]
+#[belongs_to(Word)]
+#[belongs_to(User)]
+#[primary_key(word_id, kanji)]
+#[table_name = "word_kanji"]
+pub struct WordKanji {
+    pub word_id: i32,
+    pub user_id: i32,
+    pub kanji: String,
+}
+
+#[derive(Insertable)]
+#[table_name = "word_kanji"]
+pub struct NewWordKanji {
+    pub word_id: i32,
+    pub user_id: i32,
+    pub kanji: String,
+}
+
+/// How far a user got with a kanji, across the words of their language.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct KanjiStatistics {
+    pub kanji: String,
+    /// When the first word containing the kanji was added.
+    pub first_seen_at: NaiveDateTime,
+    pub word_count: i64,
+    pub mined_word_count: i64,
+}
+
+impl WordKanji {
+    /// Breaks a new word down into its kanji.
+    pub fn insert_for_word(database_connection: &PgConnection, word: &Word) -> Result<(), Error> {
+        let new_word_kanji = extract_kanji(&word.dictionary_form)
+            .into_iter()
+            .map(|kanji| NewWordKanji {
+                word_id: word.id,
+                user_id: word.user_id,
+                kanji,
+            })
+            .collect::<Vec<NewWordKanji>>();
+
+        diesel::insert_into(word_kanji::table)
+            .values(&new_word_kanji)
+            .execute(database_connection)?;
+
+        Ok(())
+    }
+
+    /// Kanji of the words the user mined in their language.
+    pub fn get_known_kanji(
+        database_connection: &PgConnection,
+        user: &User,
+    ) -> Result<HashSet<String>, Error> {
+        let known_kanji = WordKanji::belonging_to(user)
+            .inner_join(words::table)
+            .filter(words::language.eq(user.language))
+            .filter(words::is_mined.eq(true))
+            .select(word_kanji::kanji)
+            .distinct()
+            .load::<String>(database_connection)?;
+
+        Ok(known_kanji.into_iter().collect())
+    }
+
+    /// Statistics of every kanji of the user's words in their language, from the first seen.
+    pub fn get_statistics(
+        database_connection: &PgConnection,
+        user: &User,
+    ) -> Result<Vec<KanjiStatistics>, Error> {
+        Self::load_statistics(database_connection, user, None)
+    }
+
+    pub fn get_statistics_for_kanji(
+        database_connection: &PgConnection,
+        user: &User,
+        kanji: &str,
+    ) -> Result<Option<KanjiStatistics>, Error> {
+        Ok(
+            Self::load_statistics(database_connection, user, Some(kanji))?
+                .into_iter()
+                .next(),
+        )
+    }
+
+    fn load_statistics(
+        database_connection: &PgConnection,
+        user: &User,
+        kanji: Option<&str>,
+    ) -> Result<Vec<KanjiStatistics>, Error> {
+        let mut query = WordKanji::belonging_to(user)
+            .inner_join(words::table)
+            .filter(words::language.eq(user.language))
+            .select((word_kanji::kanji, words::created_at, words::is_mined))
+            .into_boxed();
+
+        if let Some(kanji) = kanji {
+            query = query.filter(word_kanji::kanji.eq(kanji));
+        }
+
+        let rows: Vec<(String, NaiveDateTime, bool)> = query.load(database_connection)?;
+
+        let mut statistics: BTreeMap<String, KanjiStatistics> = BTreeMap::new();
+
+        for (kanji, created_at, is_mined) in rows {
+            let kanji_statistics =
+                statistics
+                    .entry(kanji.clone())
+                    .or_insert_with(|| KanjiStatistics {
+                        kanji,
+                        first_seen_at: created_at,
+                        word_count: 0,
+                        mined_word_count: 0,
+                    });
+
+            kanji_statistics.first_seen_at = kanji_statistics.first_seen_at.min(created_at);
+            kanji_statistics.word_count += 1;
+            if is_mined {
+                kanji_statistics.mined_word_count += 1;
+            }
+        }
+
+        let mut statistics = statistics.into_values().collect::<Vec<KanjiStatistics>>();
+        statistics.sort_by_key(|kanji_statistics| kanji_statistics.first_seen_at);
+
+        Ok(statistics)
+    }
+
+    /// The user's words containing a kanji, in their language.
+    pub fn get_words(
+        database_connection: &PgConnection,
+        user: &User,
+        kanji: &str,
+    ) -> Result<Vec<Word>, Error> {
+        WordKanji::belonging_to(user)
+            .inner_join(words::table)
+            .filter(words::language.eq(user.language))
+            .filter(word_kanji::kanji.eq(kanji))
+            .select(words::all_columns)
+            .order(words::created_at)
+            .load::<Word>(database_connection)
+    }
+}
@@ -0,0 +1,33 @@
+use crate::helpers::get_registration_mode;
+use std::str::FromStr;
+
+/// Who may create an account through `/auth/register`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RegistrationMode {
+    Open,
+    /// Registering takes an invite code created by an existing user.
+    InviteOnly,
+    Closed,
+}
+
+impl FromStr for RegistrationMode {
+    type Err = String;
+
+    fn from_str(registration_mode: &str) -> Result<Self, Self::Err> {
+        match registration_mode.trim() {
+            "open" => Ok(RegistrationMode::Open),
+            "invite_only" => Ok(RegistrationMode::InviteOnly),
+            "closed" => Ok(RegistrationMode::Closed),
+            _ => Err(format!(
+                "\"{}\" is not a registration mode",
+                registration_mode
+            )),
+        }
+    }
+}
+
+pub fn init_registration_mode() -> RegistrationMode {
+    get_registration_mode()
+        .parse()
+        .expect("registration mode should be open, invite_only or closed")
+}
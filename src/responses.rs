@@ -1,6 +1,6 @@
 use std::io::Cursor;
 
-use rocket::http::{ContentType, Status};
+use rocket::http::{ContentType, Header, Status};
 use rocket::request::Request;
 use rocket::response::{self, Responder, Response};
 use rocket::serde::{Deserialize, Serialize};
@@ -88,6 +88,25 @@ impl<'r> Responder<'r, 'static> for ErrorResponse {
     }
 }
 
+pub struct ResponseWithHeader<R> {
+    responder: R,
+    header: Header<'static>,
+}
+
+impl<R> ResponseWithHeader<R> {
+    pub fn new(responder: R, header: Header<'static>) -> ResponseWithHeader<R> {
+        ResponseWithHeader { responder, header }
+    }
+}
+
+impl<'r, R: Responder<'r, 'static>> Responder<'r, 'static> for ResponseWithHeader<R> {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        let mut response = self.responder.respond_to(request)?;
+        response.set_header(self.header);
+        Ok(response)
+    }
+}
+
 fn generate_response<T: Serialize>(
     responder: &T,
     http_status: Status,
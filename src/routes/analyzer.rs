@@ -1,30 +1,720 @@
-use crate::analyzer::{analyze_sentence, Morpheme};
+use crate::analyzer::cache::{AnalysisCache, AnalysisCacheStatistics, CacheStatus};
+use crate::analyzer::furigana::{render_sentence_furigana, SentenceFurigana};
+use crate::analyzer::sentence_splitter::split_sentences;
+use crate::analyzer::{analyze_sentence, Analyzer, Analyzers, Morpheme};
+use crate::database::{DbConnection, Pool};
+use crate::difficulty::{word_rarity, DifficultyWeights};
 use crate::field_validator::validate;
-use crate::models::user::User;
-use crate::responses::{ResponseResult, SuccessResponse};
+use crate::frequency_list::bands::FrequencyBands;
+use crate::frequency_list::jlpt::{JlptLevel, JlptList};
+use crate::frequency_list::{FrequencyList, FrequencyLists};
+use crate::models::sentence::Sentence;
+use crate::models::user::{User, UserSentenceEntry};
+use crate::models::word::Word;
+use crate::models::word_kanji::WordKanji;
+use crate::responses::{ErrorResponse, ResponseResult, ResponseWithHeader, SuccessResponse};
+use crate::scope::{Analyze, ScopedUser};
+use diesel::result::Error;
+use diesel::{Connection, PgConnection};
+use itertools::Itertools;
+use rocket::http::{Header, Status};
 use rocket::serde::json::Json;
 use rocket::serde::{Deserialize, Serialize};
-use validator::Validate;
+use rocket::tokio::task::spawn_blocking;
+use rocket::State;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use validator::{Validate, ValidationError};
+
+const DB_ERROR_MAP_FN: fn(Error) -> ErrorResponse =
+    |_| ErrorResponse::error("Unexpected Error".to_string(), Status::InternalServerError);
+
+const ANALYSIS_CACHE_HEADER: &str = "X-Analysis-Cache";
 
 #[derive(Validate, Deserialize)]
 pub struct AnalyzeRequest {
     #[validate(length(min = 1))]
     sentence: String,
+    #[serde(default)]
+    include_features: bool,
+}
+
+#[derive(Serialize)]
+pub struct AnnotatedMorpheme {
+    #[serde(flatten)]
+    pub morpheme: Morpheme,
+    pub is_known: bool,
+    pub is_mined: bool,
+    pub mining_frequency: Option<i32>,
+    pub dictionary_frequency: usize,
+    pub dictionary_frequency_band: String,
+    pub jlpt_level: Option<JlptLevel>,
+    #[serde(skip)]
+    pub rarity: f64,
+}
+
+impl AnnotatedMorpheme {
+    pub fn new(
+        morpheme: Morpheme,
+        known_words: &HashMap<(String, String), Word>,
+        frequency_list: &dyn FrequencyList,
+        frequency_bands: &FrequencyBands,
+        jlpt_level: Option<JlptLevel>,
+    ) -> Self {
+        let known_word =
+            known_words.get(&(morpheme.dictionary_form.clone(), morpheme.reading.clone()));
+        let frequency_lookup = frequency_list.lookup(&morpheme.dictionary_form, &morpheme.reading);
+
+        AnnotatedMorpheme {
+            is_known: known_word.is_some(),
+            is_mined: known_word.is_some_and(|word| word.is_mined),
+            mining_frequency: known_word.map(|word| word.frequency),
+            dictionary_frequency: frequency_lookup.frequency,
+            dictionary_frequency_band: frequency_bands.get_band(frequency_lookup).to_string(),
+            jlpt_level,
+            rarity: word_rarity(frequency_lookup),
+            morpheme,
+        }
+    }
 }
 
 #[derive(Serialize)]
 pub struct AnalyzeResponse {
-    pub morphemes: Vec<Morpheme>,
+    pub morphemes: Vec<AnnotatedMorpheme>,
+    pub furigana: SentenceFurigana,
+    pub unknown_word_count: usize,
+    pub difficulty: f64,
+}
+
+/// The analyzer of the language the user studies.
+pub fn get_analyzer_for_user(
+    analyzers: &Analyzers,
+    user: &User,
+) -> Result<Arc<dyn Analyzer>, ErrorResponse> {
+    analyzers.get(user.language).cloned().ok_or_else(|| {
+        ErrorResponse::fail(
+            "Language Not Supported".to_string(),
+            Status::UnprocessableEntity,
+        )
+    })
+}
+
+pub async fn analyze_sentences_off_executor(
+    analyzer: &Arc<dyn Analyzer>,
+    sentences: Vec<String>,
+) -> Result<Vec<Vec<Morpheme>>, ErrorResponse> {
+    let analyzer = Arc::clone(analyzer);
+
+    spawn_blocking(move || {
+        sentences
+            .iter()
+            .map(|sentence| analyze_sentence(analyzer.as_ref(), sentence))
+            .collect()
+    })
+    .await
+    .map_err(|_| ErrorResponse::error("Unexpected Error".to_string(), Status::InternalServerError))
+}
+
+/// Takes a connection from the pool for a synchronous part of a handler. Handlers that await the
+/// analysis of sentences take their connections this way rather than through the `DbConnection`
+/// guard, so that none is held while the analyzer runs.
+pub fn get_database_connection(database_pool: &Pool) -> Result<DbConnection, ErrorResponse> {
+    database_pool.get().map(DbConnection).map_err(|_| {
+        ErrorResponse::error(
+            Status::ServiceUnavailable.to_string(),
+            Status::ServiceUnavailable,
+        )
+    })
+}
+
+pub fn get_cached_analyses(
+    analysis_cache: &AnalysisCache,
+    database_connection: &PgConnection,
+    configuration_key: &str,
+    sentences: &[String],
+) -> Result<Vec<Option<Vec<Morpheme>>>, ErrorResponse> {
+    analysis_cache
+        .get_all(database_connection, configuration_key, sentences)
+        .map_err(DB_ERROR_MAP_FN)
+}
+
+pub async fn analyze_uncached_sentences(
+    analyzer: &Arc<dyn Analyzer>,
+    sentences: &[String],
+    cached_analyses: Vec<Option<Vec<Morpheme>>>,
+) -> Result<Vec<(Vec<Morpheme>, CacheStatus)>, ErrorResponse> {
+    let uncached_sentences = sentences
+        .iter()
+        .zip(&cached_analyses)
+        .filter(|(_, cached_analysis)| cached_analysis.is_none())
+        .map(|(sentence, _)| sentence.clone())
+        .collect();
+
+    let mut analyzed_sentences = analyze_sentences_off_executor(analyzer, uncached_sentences)
+        .await?
+        .into_iter();
+
+    Ok(cached_analyses
+        .into_iter()
+        .map(|cached_analysis| match cached_analysis {
+            Some(morphemes) => (morphemes, CacheStatus::Hit),
+            None => (
+                analyzed_sentences.next().unwrap_or_default(),
+                CacheStatus::Miss,
+            ),
+        })
+        .collect())
+}
+
+pub fn cache_analyses(
+    analysis_cache: &AnalysisCache,
+    database_connection: &PgConnection,
+    configuration_key: &str,
+    sentences: &[String],
+    analyses: &[(Vec<Morpheme>, CacheStatus)],
+) -> Result<(), ErrorResponse> {
+    let uncached_analyses = sentences
+        .iter()
+        .zip(analyses)
+        .filter(|(_, (_, cache_status))| *cache_status == CacheStatus::Miss)
+        .map(|(sentence, (morphemes, _))| (sentence.as_str(), morphemes.as_slice()))
+        // A sentence can only be upserted once per statement.
+        .unique_by(|(sentence, _)| *sentence)
+        .collect::<Vec<(&str, &[Morpheme])>>();
+
+    analysis_cache
+        .insert_all(database_connection, configuration_key, &uncached_analyses)
+        .map_err(DB_ERROR_MAP_FN)
+}
+
+/// Annotates the morphemes of several sentences, looking the user's words and custom frequency
+/// list up once for all of them.
+pub fn annotate_sentences(
+    database_connection: &PgConnection,
+    user: &User,
+    frequency_list: &dyn FrequencyList,
+    frequency_bands: &FrequencyBands,
+    jlpt_list: &JlptList,
+    sentences_morphemes: Vec<Vec<Morpheme>>,
+) -> Result<Vec<Vec<AnnotatedMorpheme>>, ErrorResponse> {
+    let dictionary_forms = sentences_morphemes
+        .iter()
+        .flatten()
+        .map(|morpheme| morpheme.dictionary_form.clone())
+        .unique()
+        .collect::<Vec<String>>();
+
+    let known_words = user
+        .get_words_by_dictionary_forms(database_connection, &dictionary_forms)
+        .map_err(DB_ERROR_MAP_FN)?;
+    let custom_frequency_list = user
+        .get_custom_frequency_list(database_connection, &dictionary_forms)
+        .map_err(DB_ERROR_MAP_FN)?;
+    let frequency_list = custom_frequency_list.as_deref().unwrap_or(frequency_list);
+
+    Ok(sentences_morphemes
+        .into_iter()
+        .map(|morphemes| {
+            morphemes
+                .into_iter()
+                .map(|morpheme| {
+                    let jlpt_level = jlpt_list.lookup(
+                        user.language,
+                        &morpheme.dictionary_form,
+                        Some(&morpheme.reading),
+                    );
+
+                    AnnotatedMorpheme::new(
+                        morpheme,
+                        &known_words,
+                        frequency_list,
+                        frequency_bands,
+                        jlpt_level,
+                    )
+                })
+                .collect()
+        })
+        .collect())
+}
+
+pub fn annotate_morphemes(
+    database_connection: &PgConnection,
+    user: &User,
+    frequency_list: &dyn FrequencyList,
+    frequency_bands: &FrequencyBands,
+    jlpt_list: &JlptList,
+    morphemes: Vec<Morpheme>,
+) -> Result<Vec<AnnotatedMorpheme>, ErrorResponse> {
+    Ok(annotate_sentences(
+        database_connection,
+        user,
+        frequency_list,
+        frequency_bands,
+        jlpt_list,
+        vec![morphemes],
+    )?
+    .into_iter()
+    .next()
+    .unwrap_or_default())
+}
+
+#[derive(Serialize)]
+pub struct UnknownWord {
+    pub dictionary_form: String,
+    pub reading: String,
+    pub dictionary_frequency: usize,
+    pub jlpt_level: Option<JlptLevel>,
+}
+
+pub fn collect_unknown_words(
+    morphemes: &[AnnotatedMorpheme],
+    is_unknown: impl Fn(&AnnotatedMorpheme) -> bool,
+) -> Vec<UnknownWord> {
+    let mut seen_words: HashSet<(&str, &str)> = HashSet::new();
+
+    morphemes
+        .iter()
+        .filter(|morpheme| morpheme.morpheme.part_of_speech.is_content_word())
+        .filter(|morpheme| is_unknown(morpheme))
+        .filter(|morpheme| {
+            seen_words.insert((
+                morpheme.morpheme.dictionary_form.as_str(),
+                morpheme.morpheme.reading.as_str(),
+            ))
+        })
+        .map(|morpheme| UnknownWord {
+            dictionary_form: morpheme.morpheme.dictionary_form.clone(),
+            reading: morpheme.morpheme.reading.clone(),
+            dictionary_frequency: morpheme.dictionary_frequency,
+            jlpt_level: morpheme.jlpt_level,
+        })
+        .collect()
 }
 
+/// Difficulty of a sentence from the rarity of its content words and the ones the user does not
+/// know.
+pub fn score_difficulty(
+    sentence: &str,
+    morphemes: &[AnnotatedMorpheme],
+    difficulty_weights: &DifficultyWeights,
+) -> f64 {
+    let content_word_rarities = morphemes
+        .iter()
+        .filter(|morpheme| morpheme.morpheme.part_of_speech.is_content_word())
+        .map(|morpheme| morpheme.rarity)
+        .collect::<Vec<f64>>();
+    let unknown_word_count = collect_unknown_words(morphemes, |morpheme| !morpheme.is_known).len();
+
+    difficulty_weights.score(sentence, &content_word_rarities, unknown_word_count)
+}
+
+#[allow(clippy::too_many_arguments)]
 #[post("/analyze", format = "json", data = "<analyze_request>")]
-pub fn analyze(
+pub async fn analyze(
     analyze_request: Json<AnalyzeRequest>,
-    _user: User,
-) -> ResponseResult<AnalyzeResponse> {
+    database_pool: &State<Pool>,
+    user: ScopedUser<Analyze>,
+    analyzers: &State<Analyzers>,
+    analysis_cache: &State<AnalysisCache>,
+    frequency_lists: &State<FrequencyLists>,
+    frequency_bands: &State<FrequencyBands>,
+    jlpt_list: &State<JlptList>,
+    difficulty_weights: &State<DifficultyWeights>,
+) -> Result<ResponseWithHeader<SuccessResponse<AnalyzeResponse>>, ErrorResponse> {
+    let analyzer = get_analyzer_for_user(analyzers, &user)?;
+    let frequency_list = frequency_lists.get_for_user(&user);
     let analyze_data = validate(analyze_request)?;
+    let include_features = analyze_data.include_features;
+
+    let sentences = vec![analyze_data.sentence.clone()];
+    let configuration_key = analyzer.configuration_key();
+
+    let cached_analyses = {
+        let database_connection = get_database_connection(database_pool)?;
+        get_cached_analyses(
+            analysis_cache,
+            &database_connection,
+            &configuration_key,
+            &sentences,
+        )?
+    };
+    let analyses = analyze_uncached_sentences(&analyzer, &sentences, cached_analyses).await?;
+    let database_connection = get_database_connection(database_pool)?;
+    cache_analyses(
+        analysis_cache,
+        &database_connection,
+        &configuration_key,
+        &sentences,
+        &analyses,
+    )?;
+
+    let (mut morphemes, cache_status) = analyses
+        .into_iter()
+        .next()
+        .unwrap_or((vec![], CacheStatus::Miss));
+
+    let furigana = render_sentence_furigana(&morphemes);
+
+    if !include_features {
+        for morpheme in &mut morphemes {
+            morpheme.features = None;
+        }
+    }
+
+    let morphemes = annotate_morphemes(
+        &database_connection,
+        &user,
+        frequency_list,
+        frequency_bands,
+        jlpt_list,
+        morphemes,
+    )?;
+    let unknown_word_count = collect_unknown_words(&morphemes, |morpheme| !morpheme.is_known).len();
+    let difficulty = score_difficulty(&analyze_data.sentence, &morphemes, difficulty_weights);
+
+    Ok(ResponseWithHeader::new(
+        SuccessResponse::new(AnalyzeResponse {
+            morphemes,
+            furigana,
+            unknown_word_count,
+            difficulty,
+        }),
+        Header::new(ANALYSIS_CACHE_HEADER, cache_status.as_str()),
+    ))
+}
+
+#[get("/analyze/cache")]
+pub fn get_analysis_cache_statistics(
+    _user: ScopedUser<Analyze>,
+    analysis_cache: &State<AnalysisCache>,
+) -> ResponseResult<AnalysisCacheStatistics> {
+    Ok(SuccessResponse::new(analysis_cache.get_statistics()))
+}
+
+fn validate_candidate_sentences(sentences: &[String]) -> Result<(), ValidationError> {
+    if sentences.iter().any(|sentence| sentence.trim().is_empty()) {
+        return Err(ValidationError::new("empty_sentence"));
+    }
+
+    Ok(())
+}
+
+#[derive(Validate, Deserialize)]
+pub struct EvaluateRequest {
+    #[validate(length(min = 1, max = 50), custom = "validate_candidate_sentences")]
+    sentences: Vec<String>,
+    #[serde(default)]
+    add_to_pending: bool,
+}
+
+#[derive(Serialize)]
+pub struct TargetWord {
+    pub dictionary_form: String,
+    pub reading: String,
+}
+
+#[derive(Serialize)]
+pub struct SentenceEvaluation {
+    pub sentence: String,
+    pub unknown_words: Vec<UnknownWord>,
+    pub is_i_plus_one: bool,
+    pub target_word: Option<TargetWord>,
+    pub pending_sentence: Option<UserSentenceEntry>,
+    pub difficulty: f64,
+}
+
+impl SentenceEvaluation {
+    pub fn new(sentence: String, morphemes: &[AnnotatedMorpheme], difficulty: f64) -> Self {
+        let unknown_words = collect_unknown_words(morphemes, |morpheme| !morpheme.is_mined);
+
+        let is_i_plus_one = unknown_words.len() == 1;
+        let target_word = if is_i_plus_one {
+            unknown_words.first().map(|unknown_word| TargetWord {
+                dictionary_form: unknown_word.dictionary_form.clone(),
+                reading: unknown_word.reading.clone(),
+            })
+        } else {
+            None
+        };
+
+        SentenceEvaluation {
+            sentence,
+            unknown_words,
+            is_i_plus_one,
+            target_word,
+            pending_sentence: None,
+            difficulty,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct EvaluateResponse {
+    pub sentences: Vec<SentenceEvaluation>,
+}
+
+enum AddPendingSentencesError {
+    DatabaseError(Error),
+    PendingSentencesLimitReached,
+}
+
+impl From<Error> for AddPendingSentencesError {
+    fn from(err: Error) -> AddPendingSentencesError {
+        AddPendingSentencesError::DatabaseError(err)
+    }
+}
+
+fn add_pending_sentences(
+    database_connection: &PgConnection,
+    user: &User,
+    frequency_list: &dyn FrequencyList,
+    frequency_bands: &FrequencyBands,
+    jlpt_list: &JlptList,
+    evaluations: &mut [SentenceEvaluation],
+) -> Result<(), AddPendingSentencesError> {
+    let target_dictionary_forms = evaluations
+        .iter()
+        .filter_map(|evaluation| evaluation.target_word.as_ref())
+        .map(|target_word| target_word.dictionary_form.clone())
+        .collect::<Vec<String>>();
+    let custom_frequency_list =
+        user.get_custom_frequency_list(database_connection, &target_dictionary_forms)?;
+    let frequency_list = custom_frequency_list.as_deref().unwrap_or(frequency_list);
+    let known_kanji = WordKanji::get_known_kanji(database_connection, user)?;
+
+    database_connection.transaction(|| {
+        for evaluation in evaluations.iter_mut() {
+            let target_word = match &evaluation.target_word {
+                None => continue,
+                Some(target_word) => target_word,
+            };
+
+            if user.is_pending_sentence_limit_reached(database_connection)? {
+                return Err(AddPendingSentencesError::PendingSentencesLimitReached);
+            }
+
+            let word_entry = Word::new_or_increase_frequency(
+                database_connection,
+                user,
+                &target_word.dictionary_form,
+                &target_word.reading,
+            )?;
+            let sentence_entry =
+                Sentence::new(database_connection, user, &word_entry, &evaluation.sentence)?;
+
+            evaluation.pending_sentence = Some(UserSentenceEntry::new(
+                &word_entry,
+                &sentence_entry,
+                frequency_list,
+                frequency_bands,
+                jlpt_list,
+                &known_kanji,
+                Some(evaluation.difficulty),
+            ));
+        }
+
+        Ok(())
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+#[post("/analyze/evaluate", format = "json", data = "<evaluate_request>")]
+pub async fn evaluate(
+    evaluate_request: Json<EvaluateRequest>,
+    database_pool: &State<Pool>,
+    user: ScopedUser<Analyze>,
+    analyzers: &State<Analyzers>,
+    analysis_cache: &State<AnalysisCache>,
+    frequency_lists: &State<FrequencyLists>,
+    frequency_bands: &State<FrequencyBands>,
+    jlpt_list: &State<JlptList>,
+    difficulty_weights: &State<DifficultyWeights>,
+) -> ResponseResult<EvaluateResponse> {
+    let analyzer = get_analyzer_for_user(analyzers, &user)?;
+    let frequency_list = frequency_lists.get_for_user(&user);
+    let evaluate_data = validate(evaluate_request)?;
+
+    let sentences = evaluate_data
+        .sentences
+        .iter()
+        .map(|sentence| sentence.trim().to_string())
+        .collect::<Vec<String>>();
+
+    let configuration_key = analyzer.configuration_key();
+
+    let cached_analyses = {
+        let database_connection = get_database_connection(database_pool)?;
+        get_cached_analyses(
+            analysis_cache,
+            &database_connection,
+            &configuration_key,
+            &sentences,
+        )?
+    };
+    let analyzed_sentences =
+        analyze_uncached_sentences(&analyzer, &sentences, cached_analyses).await?;
+    let database_connection = get_database_connection(database_pool)?;
+    cache_analyses(
+        analysis_cache,
+        &database_connection,
+        &configuration_key,
+        &sentences,
+        &analyzed_sentences,
+    )?;
+
+    let sentences_morphemes = annotate_sentences(
+        &database_connection,
+        &user,
+        frequency_list,
+        frequency_bands,
+        jlpt_list,
+        analyzed_sentences
+            .into_iter()
+            .map(|(morphemes, _)| morphemes)
+            .collect(),
+    )?;
+
+    let mut evaluations = vec![];
+    for (sentence, morphemes) in sentences.into_iter().zip(sentences_morphemes) {
+        let difficulty = score_difficulty(&sentence, &morphemes, difficulty_weights);
+        evaluations.push(SentenceEvaluation::new(sentence, &morphemes, difficulty));
+    }
+
+    if evaluate_data.add_to_pending {
+        add_pending_sentences(
+            &database_connection,
+            &user,
+            frequency_list,
+            frequency_bands,
+            jlpt_list,
+            &mut evaluations,
+        )
+        .map_err(|err| match err {
+            AddPendingSentencesError::DatabaseError(err) => DB_ERROR_MAP_FN(err),
+            AddPendingSentencesError::PendingSentencesLimitReached => ErrorResponse::fail(
+                "Pending Sentences Limit Reached".to_string(),
+                Status::TooManyRequests,
+            ),
+        })?;
+    }
+
+    Ok(SuccessResponse::new(EvaluateResponse {
+        sentences: evaluations,
+    }))
+}
+
+#[derive(Deserialize, Copy, Clone, Eq, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TextSentenceOrder {
+    #[default]
+    Text,
+    Candidates,
+}
+
+#[derive(Validate, Deserialize)]
+pub struct AnalyzeTextRequest {
+    #[validate(length(min = 1, max = 100000))]
+    text: String,
+    #[serde(default)]
+    order: TextSentenceOrder,
+}
+
+#[derive(Serialize)]
+pub struct TextSentence {
+    pub sentence: String,
+    pub unknown_word_count: usize,
+    pub unknown_words: Vec<UnknownWord>,
+}
+
+impl TextSentence {
+    fn candidate_order_key(&self) -> (bool, usize, usize) {
+        let best_dictionary_frequency = self
+            .unknown_words
+            .iter()
+            .map(|unknown_word| unknown_word.dictionary_frequency)
+            .min()
+            .unwrap_or(usize::MAX);
+
+        (
+            self.unknown_word_count == 0,
+            self.unknown_word_count,
+            best_dictionary_frequency,
+        )
+    }
+}
+
+#[derive(Serialize)]
+pub struct AnalyzeTextResponse {
+    pub sentences: Vec<TextSentence>,
+}
+
+#[allow(clippy::too_many_arguments)]
+#[post("/analyze/text", format = "json", data = "<analyze_text_request>")]
+pub async fn analyze_text(
+    analyze_text_request: Json<AnalyzeTextRequest>,
+    database_pool: &State<Pool>,
+    user: ScopedUser<Analyze>,
+    analyzers: &State<Analyzers>,
+    analysis_cache: &State<AnalysisCache>,
+    frequency_lists: &State<FrequencyLists>,
+    frequency_bands: &State<FrequencyBands>,
+    jlpt_list: &State<JlptList>,
+) -> ResponseResult<AnalyzeTextResponse> {
+    let analyzer = get_analyzer_for_user(analyzers, &user)?;
+    let frequency_list = frequency_lists.get_for_user(&user);
+    let analyze_text_data = validate(analyze_text_request)?;
+
+    let sentences = split_sentences(&analyze_text_data.text);
+    let configuration_key = analyzer.configuration_key();
+
+    let cached_analyses = {
+        let database_connection = get_database_connection(database_pool)?;
+        get_cached_analyses(
+            analysis_cache,
+            &database_connection,
+            &configuration_key,
+            &sentences,
+        )?
+    };
+    let analyzed_sentences =
+        analyze_uncached_sentences(&analyzer, &sentences, cached_analyses).await?;
+    let database_connection = get_database_connection(database_pool)?;
+    cache_analyses(
+        analysis_cache,
+        &database_connection,
+        &configuration_key,
+        &sentences,
+        &analyzed_sentences,
+    )?;
+
+    let sentences_morphemes = annotate_sentences(
+        &database_connection,
+        &user,
+        frequency_list,
+        frequency_bands,
+        jlpt_list,
+        analyzed_sentences
+            .into_iter()
+            .map(|(morphemes, _)| morphemes)
+            .collect(),
+    )?;
+
+    let mut text_sentences = vec![];
+    for (sentence, morphemes) in sentences.into_iter().zip(sentences_morphemes) {
+        let unknown_words = collect_unknown_words(&morphemes, |morpheme| !morpheme.is_known);
+
+        text_sentences.push(TextSentence {
+            sentence,
+            unknown_word_count: unknown_words.len(),
+            unknown_words,
+        });
+    }
+
+    if analyze_text_data.order == TextSentenceOrder::Candidates {
+        text_sentences.sort_by_key(|text_sentence| text_sentence.candidate_order_key());
+    }
 
-    Ok(SuccessResponse::new(AnalyzeResponse {
-        morphemes: analyze_sentence(&analyze_data.sentence),
+    Ok(SuccessResponse::new(AnalyzeTextResponse {
+        sentences: text_sentences,
     }))
 }
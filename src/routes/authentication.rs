@@ -1,12 +1,23 @@
 use crate::database;
 use crate::field_validator::validate;
-use crate::jwt::{generate_token, token_error_to_response, validate_token, TokenType};
-use crate::models::user::{User, UserRegistrationError};
+use crate::jwt::{
+    generate_token, token_error_to_response, validate_token_claims, TokenError, TokenType,
+};
+use crate::models::personal_access_token::PersonalAccessToken;
+use crate::models::session::{Session, SessionClient};
+use crate::models::user::{ChangePasswordError, User, UserRegistrationError};
+use crate::registration::RegistrationMode;
 use crate::responses::{ErrorResponse, ResponseResult, SuccessResponse};
+use diesel::result::Error;
+use diesel::Connection;
 use rocket::http::Status;
 use rocket::serde::{json::Json, Deserialize, Serialize};
+use rocket::State;
 use validator::Validate;
 
+const DB_ERROR_MAP_FN: fn(Error) -> ErrorResponse =
+    |_| ErrorResponse::error("Unexpected Error".to_string(), Status::InternalServerError);
+
 #[derive(Validate, Deserialize)]
 pub struct RegisterRequest {
     #[validate(length(min = 3))]
@@ -15,58 +26,121 @@ pub struct RegisterRequest {
     email: String,
     #[validate(length(min = 8))]
     password: String,
+    /// Required while registration is invite-only, ignored otherwise.
+    invite_code: Option<String>,
 }
 
 #[post("/auth/register", format = "json", data = "<register_request>")]
 pub fn register(
     register_request: Json<RegisterRequest>,
     database_connection: database::DbConnection,
+    registration_mode: &State<RegistrationMode>,
 ) -> ResponseResult<User> {
+    if **registration_mode == RegistrationMode::Closed {
+        return Err(ErrorResponse::fail(
+            "Registration Closed".to_string(),
+            Status::Forbidden,
+        ));
+    }
+
     let register_data = validate(register_request)?;
 
     let username = register_data.username.trim().to_lowercase();
     let email = register_data.email.trim().to_lowercase();
 
-    let registration_result = User::register(
-        &database_connection,
-        username,
-        email,
-        register_data.password,
-    );
+    let registration_result = match **registration_mode {
+        RegistrationMode::InviteOnly => User::register_with_invite(
+            &database_connection,
+            username,
+            email,
+            register_data.password,
+            register_data
+                .invite_code
+                .as_deref()
+                .unwrap_or_default()
+                .trim(),
+        ),
+        _ => User::register(
+            &database_connection,
+            username,
+            email,
+            register_data.password,
+        ),
+    };
 
     match registration_result {
         Ok(user) => Ok(SuccessResponse::new(user)),
+        Err(UserRegistrationError::InvalidInviteCode) => Err(ErrorResponse::fail(
+            "Invalid Invite Code".to_string(),
+            Status::Forbidden,
+        )),
         Err(error) => Err(ErrorResponse::fail_with_reasons(
             "Validation Error".to_string(),
             vec![match error {
                 UserRegistrationError::DuplicateEmail => "duplicate email".to_string(),
                 UserRegistrationError::DuplicateUsername => "duplicate username".to_string(),
                 UserRegistrationError::FailedToHash => "password hash failed".to_string(),
+                UserRegistrationError::InvalidInviteCode => "invalid invite code".to_string(),
             }],
             Status::Conflict,
         )),
     }
 }
 
+#[derive(Serialize)]
+pub struct TokenPairResponse {
+    access_token: String,
+    refresh_token: String,
+}
+
+fn generate_token_pair(user: &User, session: &Session) -> Result<TokenPairResponse, ErrorResponse> {
+    let error_map_fn = || {
+        ErrorResponse::error(
+            "Failed to sign JWT".to_string(),
+            Status::InternalServerError,
+        )
+    };
+    let access_token =
+        generate_token(user, TokenType::Access, Some(session)).ok_or_else(error_map_fn)?;
+    let refresh_token =
+        generate_token(user, TokenType::Refresh, Some(session)).ok_or_else(error_map_fn)?;
+
+    Ok(TokenPairResponse {
+        access_token,
+        refresh_token,
+    })
+}
+
+/// Opens a session for the client and issues its first token pair.
+fn start_session(
+    database_connection: &database::DbConnection,
+    user: &User,
+    device_name: Option<String>,
+    client: &SessionClient,
+) -> Result<TokenPairResponse, ErrorResponse> {
+    let session =
+        Session::new(database_connection, user, device_name, client).map_err(DB_ERROR_MAP_FN)?;
+
+    generate_token_pair(user, &session)
+}
+
 #[derive(Validate, Deserialize)]
 pub struct LoginRequest {
     #[validate(email)]
     email: String,
     #[validate(length(min = 1))]
     password: String,
-}
-
-#[derive(Serialize)]
-pub struct LoginResponse {
-    access_token: String,
-    refresh_token: String,
+    /// Name the session is listed under, e.g. "Work laptop".
+    #[validate(length(min = 1, max = 100))]
+    device_name: Option<String>,
 }
 
 #[post("/auth/login", format = "json", data = "<login_request>")]
 pub fn login(
     login_request: Json<LoginRequest>,
     database_connection: database::DbConnection,
-) -> ResponseResult<LoginResponse> {
+    client: SessionClient,
+) -> ResponseResult<TokenPairResponse> {
     let login_data = validate(login_request)?;
 
     let email = login_data.email.trim().to_lowercase();
@@ -77,19 +151,12 @@ pub fn login(
             ErrorResponse::fail("Invalid Credentials".to_string(), Status::Unauthorized)
         })?;
 
-    let error_map_fn = || {
-        ErrorResponse::error(
-            "Failed to sign JWT".to_string(),
-            Status::InternalServerError,
-        )
-    };
-    let access_token = generate_token(&user, TokenType::Access).ok_or_else(error_map_fn)?;
-    let refresh_token = generate_token(&user, TokenType::Refresh).ok_or_else(error_map_fn)?;
-
-    Ok(SuccessResponse::new(LoginResponse {
-        access_token,
-        refresh_token,
-    }))
+    Ok(SuccessResponse::new(start_session(
+        &database_connection,
+        &user,
+        login_data.device_name,
+        &client,
+    )?))
 }
 
 #[derive(Validate, Deserialize)]
@@ -98,38 +165,117 @@ pub struct RefreshRequest {
     refresh_token: String,
 }
 
-#[derive(Serialize)]
-pub struct RefreshResponse {
-    access_token: String,
-    refresh_token: String,
-}
-
 #[post("/auth/refresh", format = "json", data = "<refresh_request>")]
 pub fn refresh(
     refresh_request: Json<RefreshRequest>,
     database_connection: database::DbConnection,
-) -> ResponseResult<RefreshResponse> {
+    client: SessionClient,
+) -> ResponseResult<TokenPairResponse> {
     let refresh_data = validate(refresh_request)?;
-    let user = validate_token(
+    let (user, claims) = validate_token_claims(
         refresh_data.refresh_token,
         TokenType::Refresh,
         &database_connection,
     )
     .map_err(|error| token_error_to_response(&error))?;
 
-    let error_map_fn = || {
-        ErrorResponse::error(
-            "Failed to sign JWT".to_string(),
-            Status::InternalServerError,
-        )
+    // Refresh tokens issued before sessions existed belong to none, and replaying one could
+    // otherwise open any number of them, so their users have to log in again.
+    let session_id = claims
+        .sid
+        .ok_or_else(|| token_error_to_response(&TokenError::RevokedSession))?;
+    let session = user
+        .get_session_by_id(&database_connection, session_id)
+        .ok_or_else(|| token_error_to_response(&TokenError::RevokedSession))?;
+
+    let session = match session
+        .rotate(&database_connection, claims.jti.as_deref(), &client)
+        .map_err(DB_ERROR_MAP_FN)?
+    {
+        Some(session) => session,
+        // A rotated refresh token being used again means it has leaked, so the session and with
+        // it every token issued from it is revoked.
+        None => {
+            session
+                .delete(&database_connection)
+                .map_err(DB_ERROR_MAP_FN)?;
+            return Err(token_error_to_response(&TokenError::Reused));
+        }
     };
-    let access_token = generate_token(&user, TokenType::Access).ok_or_else(error_map_fn)?;
-    let refresh_token = generate_token(&user, TokenType::Refresh).ok_or_else(error_map_fn)?;
 
-    Ok(SuccessResponse::new(RefreshResponse {
-        access_token,
-        refresh_token,
-    }))
+    Ok(SuccessResponse::new(generate_token_pair(&user, &session)?))
+}
+
+/// Revokes every token, session and personal access token of the user, including the ones of
+/// the request, in exchange for a new session.
+#[post("/auth/logout-all")]
+pub fn logout_all(
+    database_connection: database::DbConnection,
+    mut user: User,
+    client: SessionClient,
+) -> ResponseResult<TokenPairResponse> {
+    database_connection
+        .transaction(|| {
+            user.increment_token_generation(&database_connection)?;
+            Session::delete_all_for_user(&database_connection, &user)?;
+            PersonalAccessToken::delete_all_for_user(&database_connection, &user)
+        })
+        .map_err(DB_ERROR_MAP_FN)?;
+
+    Ok(SuccessResponse::new(start_session(
+        &database_connection,
+        &user,
+        None,
+        &client,
+    )?))
+}
+
+#[derive(Validate, Deserialize)]
+pub struct ChangePasswordRequest {
+    #[validate(length(min = 1))]
+    current_password: String,
+    #[validate(length(min = 8))]
+    new_password: String,
+}
+
+#[put("/auth/password", format = "json", data = "<change_password_request>")]
+pub fn change_password(
+    change_password_request: Json<ChangePasswordRequest>,
+    database_connection: database::DbConnection,
+    mut user: User,
+    client: SessionClient,
+) -> ResponseResult<TokenPairResponse> {
+    let change_password_data = validate(change_password_request)?;
+
+    if !user.verify_password(&change_password_data.current_password) {
+        return Err(ErrorResponse::fail(
+            "Invalid Credentials".to_string(),
+            Status::Unauthorized,
+        ));
+    }
+
+    database_connection
+        .transaction(|| {
+            user.change_password(&database_connection, change_password_data.new_password)?;
+            Session::delete_all_for_user(&database_connection, &user)?;
+            PersonalAccessToken::delete_all_for_user(&database_connection, &user)?;
+
+            Ok(())
+        })
+        .map_err(|error| match error {
+            ChangePasswordError::FailedToHash => ErrorResponse::error(
+                "Password Hash Failed".to_string(),
+                Status::InternalServerError,
+            ),
+            ChangePasswordError::DatabaseError(err) => DB_ERROR_MAP_FN(err),
+        })?;
+
+    Ok(SuccessResponse::new(start_session(
+        &database_connection,
+        &user,
+        None,
+        &client,
+    )?))
 }
 
 #[get("/auth/me")]
@@ -14,7 +14,10 @@ pub fn default(status: Status, request: &Request) -> ErrorResponse {
         | TokenError::IatInTheFuture
         | TokenError::Expired
         | TokenError::InvalidSubject
-        | TokenError::InvalidType => token_error_to_response(token_validation_error),
+        | TokenError::InvalidType
+        | TokenError::RevokedSession
+        | TokenError::InsufficientScope
+        | TokenError::DatabaseError => token_error_to_response(token_validation_error),
         _ => match status {
             s if s.code >= 400 && s.code < 500 => ErrorResponse::fail(s.to_string(), s),
             _ => ErrorResponse::error(status.to_string(), status),
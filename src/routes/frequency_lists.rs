@@ -0,0 +1,359 @@
+use crate::database::DbConnection;
+use crate::field_validator::validate;
+use crate::frequency_list::bands::{FrequencyBand, FrequencyBands};
+use crate::frequency_list::counts::read_counted_entries;
+use crate::frequency_list::jlpt::{JlptLevel, JlptList};
+use crate::frequency_list::{
+    frequency_list_from_ranked_entries, parse_frequency_list_json, FrequencyList,
+    FrequencyListInfo, FrequencyLists, FrequencyMatch,
+};
+use crate::helpers::get_maximum_frequency_list_size;
+use crate::language::Language;
+use crate::models::custom_frequency_list::CustomFrequencyList;
+use crate::models::user::User;
+use crate::responses::{ErrorResponse, ResponseResult, SuccessResponse};
+use crate::scope::{FrequencyListsRead, ScopedUser};
+use diesel::result::Error;
+use rocket::data::{Data, ToByteUnit};
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::serde::{Deserialize, Serialize};
+use rocket::State;
+use validator::{Validate, ValidationError};
+
+const DB_ERROR_MAP_FN: fn(Error) -> ErrorResponse =
+    |_| ErrorResponse::error("Unexpected Error".to_string(), Status::InternalServerError);
+
+#[derive(Serialize)]
+pub struct GetFrequencyListsResponse {
+    pub frequency_lists: Vec<FrequencyListInfo>,
+    pub selected: String,
+}
+
+fn get_selected_frequency_list(user: &User, frequency_lists: &FrequencyLists) -> String {
+    match &user.frequency_list {
+        Some(name) if frequency_lists.contains(user.language, name) => name.clone(),
+        _ => frequency_lists.get_default_name(user.language).to_string(),
+    }
+}
+
+#[get("/frequency-lists")]
+pub fn get_all(
+    user: ScopedUser<FrequencyListsRead>,
+    frequency_lists: &State<FrequencyLists>,
+) -> ResponseResult<GetFrequencyListsResponse> {
+    Ok(SuccessResponse::new(GetFrequencyListsResponse {
+        frequency_lists: frequency_lists.get_infos(user.language),
+        selected: get_selected_frequency_list(&user, frequency_lists),
+    }))
+}
+
+#[derive(Validate, Deserialize)]
+pub struct SelectFrequencyListRequest {
+    #[validate(length(min = 1))]
+    name: String,
+}
+
+#[put(
+    "/frequency-lists/selected",
+    format = "json",
+    data = "<select_request>"
+)]
+pub fn select(
+    select_request: Json<SelectFrequencyListRequest>,
+    database_connection: DbConnection,
+    mut user: User,
+    frequency_lists: &State<FrequencyLists>,
+) -> ResponseResult<GetFrequencyListsResponse> {
+    let select_data = validate(select_request)?;
+
+    if !frequency_lists.contains(user.language, &select_data.name) {
+        return Err(ErrorResponse::fail(
+            "Frequency List Not Found".to_string(),
+            Status::NotFound,
+        ));
+    }
+
+    user.set_frequency_list(&database_connection, select_data.name)
+        .map_err(DB_ERROR_MAP_FN)?;
+
+    Ok(SuccessResponse::new(GetFrequencyListsResponse {
+        frequency_lists: frequency_lists.get_infos(user.language),
+        selected: get_selected_frequency_list(&user, frequency_lists),
+    }))
+}
+
+#[derive(Serialize)]
+pub struct GetFrequencyBandsResponse {
+    pub bands: Vec<FrequencyBand>,
+}
+
+#[get("/frequency-lists/bands")]
+pub fn get_bands(
+    _user: ScopedUser<FrequencyListsRead>,
+    frequency_bands: &State<FrequencyBands>,
+) -> ResponseResult<GetFrequencyBandsResponse> {
+    Ok(SuccessResponse::new(GetFrequencyBandsResponse {
+        bands: frequency_bands.get_bands().to_vec(),
+    }))
+}
+
+#[derive(Serialize)]
+pub struct WordFrequency {
+    pub dictionary_form: String,
+    pub reading: Option<String>,
+    pub frequency: usize,
+    pub match_type: FrequencyMatch,
+    pub band: String,
+    pub jlpt_level: Option<JlptLevel>,
+}
+
+impl WordFrequency {
+    fn new(
+        dictionary_form: String,
+        reading: Option<String>,
+        frequency_list: &dyn FrequencyList,
+        frequency_bands: &FrequencyBands,
+        jlpt_list: &JlptList,
+        language: Language,
+    ) -> Self {
+        let frequency_lookup = match &reading {
+            Some(reading) => frequency_list.lookup(&dictionary_form, reading),
+            None => frequency_list.lookup_dictionary_form(&dictionary_form),
+        };
+
+        let jlpt_level = jlpt_list.lookup(language, &dictionary_form, reading.as_deref());
+
+        WordFrequency {
+            dictionary_form,
+            reading,
+            frequency: frequency_lookup.frequency,
+            match_type: frequency_lookup.match_type,
+            band: frequency_bands.get_band(frequency_lookup).to_string(),
+            jlpt_level,
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+#[get("/frequency-lists/lookup?<dictionary_form>&<reading>")]
+pub fn lookup(
+    dictionary_form: String,
+    reading: Option<String>,
+    database_connection: DbConnection,
+    user: ScopedUser<FrequencyListsRead>,
+    frequency_lists: &State<FrequencyLists>,
+    frequency_bands: &State<FrequencyBands>,
+    jlpt_list: &State<JlptList>,
+) -> ResponseResult<WordFrequency> {
+    let frequency_list = frequency_lists.get_for_user(&user);
+    let dictionary_form = dictionary_form.trim().to_string();
+
+    if dictionary_form.is_empty() {
+        return Err(ErrorResponse::fail(
+            "Dictionary Form Is Required".to_string(),
+            Status::UnprocessableEntity,
+        ));
+    }
+
+    let custom_frequency_list = user
+        .get_custom_frequency_list(&database_connection, std::slice::from_ref(&dictionary_form))
+        .map_err(DB_ERROR_MAP_FN)?;
+    let frequency_list = custom_frequency_list.as_deref().unwrap_or(frequency_list);
+
+    Ok(SuccessResponse::new(WordFrequency::new(
+        dictionary_form,
+        reading.map(|reading| reading.trim().to_string()),
+        frequency_list,
+        frequency_bands,
+        jlpt_list,
+        user.language,
+    )))
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LookupWord {
+    dictionary_form: String,
+    reading: Option<String>,
+}
+
+fn validate_lookup_words(words: &[LookupWord]) -> Result<(), ValidationError> {
+    if words
+        .iter()
+        .any(|word| word.dictionary_form.trim().is_empty())
+    {
+        return Err(ValidationError::new("empty_dictionary_form"));
+    }
+
+    Ok(())
+}
+
+#[derive(Validate, Deserialize)]
+pub struct LookupRequest {
+    #[validate(length(min = 1, max = 1000), custom = "validate_lookup_words")]
+    words: Vec<LookupWord>,
+}
+
+#[derive(Serialize)]
+pub struct LookupResponse {
+    pub words: Vec<WordFrequency>,
+}
+
+#[post("/frequency-lists/lookup", format = "json", data = "<lookup_request>")]
+pub fn lookup_many(
+    lookup_request: Json<LookupRequest>,
+    database_connection: DbConnection,
+    user: ScopedUser<FrequencyListsRead>,
+    frequency_lists: &State<FrequencyLists>,
+    frequency_bands: &State<FrequencyBands>,
+    jlpt_list: &State<JlptList>,
+) -> ResponseResult<LookupResponse> {
+    let frequency_list = frequency_lists.get_for_user(&user);
+    let lookup_data = validate(lookup_request)?;
+
+    let words = lookup_data
+        .words
+        .into_iter()
+        .map(|word| {
+            (
+                word.dictionary_form.trim().to_string(),
+                word.reading.map(|reading| reading.trim().to_string()),
+            )
+        })
+        .collect::<Vec<(String, Option<String>)>>();
+
+    let dictionary_forms = words
+        .iter()
+        .map(|(dictionary_form, _)| dictionary_form.clone())
+        .collect::<Vec<String>>();
+    let custom_frequency_list = user
+        .get_custom_frequency_list(&database_connection, &dictionary_forms)
+        .map_err(DB_ERROR_MAP_FN)?;
+    let frequency_list = custom_frequency_list.as_deref().unwrap_or(frequency_list);
+
+    Ok(SuccessResponse::new(LookupResponse {
+        words: words
+            .into_iter()
+            .map(|(dictionary_form, reading)| {
+                WordFrequency::new(
+                    dictionary_form,
+                    reading,
+                    frequency_list,
+                    frequency_bands,
+                    jlpt_list,
+                    user.language,
+                )
+            })
+            .collect(),
+    }))
+}
+
+async fn read_frequency_list_upload(data: Data<'_>) -> Result<String, ErrorResponse> {
+    let upload = data
+        .open(get_maximum_frequency_list_size().bytes())
+        .into_string()
+        .await
+        .map_err(|_| {
+            ErrorResponse::fail("Invalid Frequency List".to_string(), Status::BadRequest)
+        })?;
+
+    if !upload.is_complete() {
+        return Err(ErrorResponse::fail(
+            "Frequency List Too Large".to_string(),
+            Status::PayloadTooLarge,
+        ));
+    }
+
+    Ok(upload.into_inner())
+}
+
+fn save_custom_frequency_list(
+    database_connection: &DbConnection,
+    user: &User,
+    frequency_list: Result<Box<dyn FrequencyList>, String>,
+) -> ResponseResult<CustomFrequencyList> {
+    let frequency_list = frequency_list.map_err(|err| {
+        ErrorResponse::fail_with_reasons(
+            "Invalid Frequency List".to_string(),
+            vec![err],
+            Status::UnprocessableEntity,
+        )
+    })?;
+
+    if frequency_list.is_empty() {
+        return Err(ErrorResponse::fail(
+            "Frequency List Is Empty".to_string(),
+            Status::UnprocessableEntity,
+        ));
+    }
+
+    let custom_frequency_list =
+        CustomFrequencyList::save(database_connection, user, frequency_list.as_ref())
+            .map_err(DB_ERROR_MAP_FN)?;
+
+    Ok(SuccessResponse::new(custom_frequency_list))
+}
+
+#[put("/frequency-lists/custom", format = "json", data = "<data>")]
+pub async fn upload_custom_json(
+    data: Data<'_>,
+    database_connection: DbConnection,
+    user: User,
+) -> ResponseResult<CustomFrequencyList> {
+    let frequency_list_json = read_frequency_list_upload(data).await?;
+
+    save_custom_frequency_list(
+        &database_connection,
+        &user,
+        parse_frequency_list_json(user.language, &frequency_list_json),
+    )
+}
+
+#[put("/frequency-lists/custom", format = "text/csv", data = "<data>")]
+pub async fn upload_custom_csv(
+    data: Data<'_>,
+    database_connection: DbConnection,
+    user: User,
+) -> ResponseResult<CustomFrequencyList> {
+    let frequency_list_csv = read_frequency_list_upload(data).await?;
+
+    save_custom_frequency_list(
+        &database_connection,
+        &user,
+        read_counted_entries(frequency_list_csv.as_bytes())
+            .map(|entries| frequency_list_from_ranked_entries(user.language, entries)),
+    )
+}
+
+#[get("/frequency-lists/custom")]
+pub fn get_custom(
+    database_connection: DbConnection,
+    user: ScopedUser<FrequencyListsRead>,
+) -> ResponseResult<CustomFrequencyList> {
+    let custom_frequency_list =
+        CustomFrequencyList::find(&database_connection, user.id, user.language)
+            .map_err(DB_ERROR_MAP_FN)?
+            .ok_or_else(|| {
+                ErrorResponse::fail(
+                    "Custom Frequency List Not Found".to_string(),
+                    Status::NotFound,
+                )
+            })?;
+
+    Ok(SuccessResponse::new(custom_frequency_list))
+}
+
+#[delete("/frequency-lists/custom")]
+pub fn delete_custom(database_connection: DbConnection, user: User) -> ResponseResult {
+    let is_deleted =
+        CustomFrequencyList::delete(&database_connection, &user).map_err(DB_ERROR_MAP_FN)?;
+
+    if !is_deleted {
+        return Err(ErrorResponse::fail(
+            "Custom Frequency List Not Found".to_string(),
+            Status::NotFound,
+        ));
+    }
+
+    Ok(SuccessResponse::new(()))
+}
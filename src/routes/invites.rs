@@ -0,0 +1,81 @@
+use crate::database::DbConnection;
+use crate::field_validator::validate;
+use crate::models::invite::Invite;
+use crate::models::user::User;
+use crate::responses::{ErrorResponse, ResponseResult, SuccessResponse};
+use chrono::{Duration, Utc};
+use diesel::result::Error;
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::serde::{Deserialize, Serialize};
+use validator::Validate;
+
+const DB_ERROR_MAP_FN: fn(Error) -> ErrorResponse =
+    |_| ErrorResponse::error("Unexpected Error".to_string(), Status::InternalServerError);
+
+fn default_uses() -> i32 {
+    1
+}
+
+#[derive(Validate, Deserialize)]
+pub struct NewInviteRequest {
+    #[validate(range(min = 1))]
+    #[serde(default = "default_uses")]
+    uses: i32,
+    /// Seconds until the invite expires, up to ten years. Invites without it never expire.
+    #[validate(range(min = 1, max = 315360000))]
+    expires_in: Option<i64>,
+}
+
+#[post("/invites", format = "json", data = "<new_invite_request>")]
+pub fn new(
+    new_invite_request: Json<NewInviteRequest>,
+    database_connection: DbConnection,
+    user: User,
+) -> ResponseResult<Invite> {
+    let new_invite_data = validate(new_invite_request)?;
+
+    let expires_at = new_invite_data
+        .expires_in
+        .map(|expires_in| Utc::now().naive_utc() + Duration::seconds(expires_in));
+
+    let invite = Invite::new(
+        &database_connection,
+        &user,
+        new_invite_data.uses,
+        expires_at,
+    )
+    .map_err(DB_ERROR_MAP_FN)?;
+
+    Ok(SuccessResponse::new(invite))
+}
+
+#[derive(Serialize)]
+pub struct GetAllInvitesResponse {
+    pub invites: Vec<Invite>,
+}
+
+#[get("/invites")]
+pub fn get_all(
+    database_connection: DbConnection,
+    user: User,
+) -> ResponseResult<GetAllInvitesResponse> {
+    let invites = user
+        .get_all_invites(&database_connection)
+        .map_err(DB_ERROR_MAP_FN)?;
+
+    Ok(SuccessResponse::new(GetAllInvitesResponse { invites }))
+}
+
+#[delete("/invites/<invite_id>")]
+pub fn delete(invite_id: i32, database_connection: DbConnection, user: User) -> ResponseResult {
+    let invite = user
+        .get_invite_by_id(&database_connection, invite_id)
+        .ok_or_else(|| ErrorResponse::fail("Invite Not Found".to_string(), Status::NotFound))?;
+
+    invite
+        .delete(&database_connection)
+        .map_err(DB_ERROR_MAP_FN)?;
+
+    Ok(SuccessResponse::new(()))
+}
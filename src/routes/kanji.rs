@@ -0,0 +1,98 @@
+use crate::database::DbConnection;
+use crate::kanji::{extract_kanji, KanjiInfo, KanjiList};
+use crate::models::word_kanji::{KanjiStatistics, WordKanji};
+use crate::responses::{ErrorResponse, ResponseResult, SuccessResponse};
+use crate::scope::{KanjiRead, ScopedUser};
+use diesel::result::Error;
+use rocket::http::Status;
+use rocket::serde::Serialize;
+use rocket::State;
+
+const DB_ERROR_MAP_FN: fn(Error) -> ErrorResponse =
+    |_| ErrorResponse::error("Unexpected Error".to_string(), Status::InternalServerError);
+
+#[derive(Serialize)]
+pub struct KanjiEntry {
+    #[serde(flatten)]
+    pub statistics: KanjiStatistics,
+    #[serde(flatten)]
+    pub info: KanjiInfo,
+}
+
+impl KanjiEntry {
+    fn new(statistics: KanjiStatistics, kanji_list: &KanjiList) -> Self {
+        KanjiEntry {
+            info: kanji_list.get(&statistics.kanji),
+            statistics,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct GetAllKanjiResponse {
+    pub kanji: Vec<KanjiEntry>,
+}
+
+#[get("/kanji")]
+pub fn get_all(
+    database_connection: DbConnection,
+    user: ScopedUser<KanjiRead>,
+    kanji_list: &State<KanjiList>,
+) -> ResponseResult<GetAllKanjiResponse> {
+    let statistics =
+        WordKanji::get_statistics(&database_connection, &user).map_err(DB_ERROR_MAP_FN)?;
+
+    Ok(SuccessResponse::new(GetAllKanjiResponse {
+        kanji: statistics
+            .into_iter()
+            .map(|statistics| KanjiEntry::new(statistics, kanji_list))
+            .collect(),
+    }))
+}
+
+#[derive(Serialize)]
+pub struct KanjiWord {
+    pub dictionary_form: String,
+    pub reading: String,
+    pub is_mined: bool,
+}
+
+#[derive(Serialize)]
+pub struct GetKanjiResponse {
+    #[serde(flatten)]
+    pub kanji: KanjiEntry,
+    pub words: Vec<KanjiWord>,
+}
+
+#[get("/kanji/<kanji>")]
+pub fn get(
+    kanji: String,
+    database_connection: DbConnection,
+    user: ScopedUser<KanjiRead>,
+    kanji_list: &State<KanjiList>,
+) -> ResponseResult<GetKanjiResponse> {
+    if extract_kanji(&kanji) != [kanji.as_str()] {
+        return Err(ErrorResponse::fail(
+            "Invalid Kanji".to_string(),
+            Status::UnprocessableEntity,
+        ));
+    }
+
+    let statistics = WordKanji::get_statistics_for_kanji(&database_connection, &user, &kanji)
+        .map_err(DB_ERROR_MAP_FN)?
+        .ok_or_else(|| ErrorResponse::fail("Kanji Not Found".to_string(), Status::NotFound))?;
+    let words =
+        WordKanji::get_words(&database_connection, &user, &kanji).map_err(DB_ERROR_MAP_FN)?;
+
+    Ok(SuccessResponse::new(GetKanjiResponse {
+        kanji: KanjiEntry::new(statistics, kanji_list),
+        words: words
+            .into_iter()
+            .map(|word| KanjiWord {
+                dictionary_form: word.dictionary_form,
+                reading: word.reading,
+                is_mined: word.is_mined,
+            })
+            .collect(),
+    }))
+}
@@ -0,0 +1,57 @@
+use crate::analyzer::Analyzers;
+use crate::database::DbConnection;
+use crate::field_validator::validate;
+use crate::language::Language;
+use crate::models::user::User;
+use crate::responses::{ErrorResponse, ResponseResult, SuccessResponse};
+use diesel::result::Error;
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::serde::{Deserialize, Serialize};
+use rocket::State;
+use validator::Validate;
+
+const DB_ERROR_MAP_FN: fn(Error) -> ErrorResponse =
+    |_| ErrorResponse::error("Unexpected Error".to_string(), Status::InternalServerError);
+
+#[derive(Serialize)]
+pub struct GetLanguagesResponse {
+    pub languages: Vec<Language>,
+    pub selected: Language,
+}
+
+#[get("/languages")]
+pub fn get_all(user: User, analyzers: &State<Analyzers>) -> ResponseResult<GetLanguagesResponse> {
+    Ok(SuccessResponse::new(GetLanguagesResponse {
+        languages: analyzers.get_languages(),
+        selected: user.language,
+    }))
+}
+
+#[derive(Validate, Deserialize)]
+pub struct SelectLanguageRequest {
+    #[validate(length(min = 1))]
+    language: String,
+}
+
+#[put("/languages/selected", format = "json", data = "<select_request>")]
+pub fn select(
+    select_request: Json<SelectLanguageRequest>,
+    database_connection: DbConnection,
+    mut user: User,
+    analyzers: &State<Analyzers>,
+) -> ResponseResult<GetLanguagesResponse> {
+    let select_data = validate(select_request)?;
+
+    let language = Language::from_code(&select_data.language)
+        .filter(|language| analyzers.get(*language).is_some())
+        .ok_or_else(|| ErrorResponse::fail("Language Not Found".to_string(), Status::NotFound))?;
+
+    user.set_language(&database_connection, language)
+        .map_err(DB_ERROR_MAP_FN)?;
+
+    Ok(SuccessResponse::new(GetLanguagesResponse {
+        languages: analyzers.get_languages(),
+        selected: user.language,
+    }))
+}
@@ -1,4 +1,10 @@
 pub mod analyzer;
 pub mod authentication;
 pub mod catcher;
+pub mod frequency_lists;
+pub mod invites;
+pub mod kanji;
+pub mod languages;
+pub mod personal_access_tokens;
 pub mod sentences;
+pub mod sessions;
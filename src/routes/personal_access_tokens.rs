@@ -0,0 +1,112 @@
+use crate::database::DbConnection;
+use crate::field_validator::validate;
+use crate::models::personal_access_token::PersonalAccessToken;
+use crate::models::user::User;
+use crate::responses::{ErrorResponse, ResponseResult, SuccessResponse};
+use crate::scope::Scope;
+use chrono::{Duration, Utc};
+use diesel::result::Error;
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::serde::{Deserialize, Serialize};
+use validator::Validate;
+
+const DB_ERROR_MAP_FN: fn(Error) -> ErrorResponse =
+    |_| ErrorResponse::error("Unexpected Error".to_string(), Status::InternalServerError);
+
+#[derive(Validate, Deserialize)]
+pub struct NewPersonalAccessTokenRequest {
+    #[validate(length(min = 1, max = 100))]
+    name: String,
+    #[validate(length(min = 1))]
+    scopes: Vec<String>,
+    /// Seconds until the token expires, up to ten years. Tokens without it never expire.
+    #[validate(range(min = 1, max = 315360000))]
+    expires_in: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct NewPersonalAccessTokenResponse {
+    #[serde(flatten)]
+    personal_access_token: PersonalAccessToken,
+    /// The token itself, only ever shown in this response.
+    token: String,
+}
+
+#[post(
+    "/auth/tokens",
+    format = "json",
+    data = "<new_personal_access_token_request>"
+)]
+pub fn new(
+    new_personal_access_token_request: Json<NewPersonalAccessTokenRequest>,
+    database_connection: DbConnection,
+    user: User,
+) -> ResponseResult<NewPersonalAccessTokenResponse> {
+    let new_personal_access_token_data = validate(new_personal_access_token_request)?;
+
+    let mut scopes = new_personal_access_token_data
+        .scopes
+        .iter()
+        .map(|scope| Scope::from_code(scope.trim()))
+        .collect::<Option<Vec<Scope>>>()
+        .ok_or_else(|| {
+            ErrorResponse::fail("Invalid Scope".to_string(), Status::UnprocessableEntity)
+        })?;
+    scopes.sort();
+    scopes.dedup();
+
+    let expires_at = new_personal_access_token_data
+        .expires_in
+        .map(|expires_in| Utc::now().naive_utc() + Duration::seconds(expires_in));
+
+    let (personal_access_token, token) = PersonalAccessToken::new(
+        &database_connection,
+        &user,
+        new_personal_access_token_data.name.trim().to_string(),
+        scopes,
+        expires_at,
+    )
+    .map_err(DB_ERROR_MAP_FN)?;
+
+    Ok(SuccessResponse::new(NewPersonalAccessTokenResponse {
+        personal_access_token,
+        token,
+    }))
+}
+
+#[derive(Serialize)]
+pub struct GetAllPersonalAccessTokensResponse {
+    pub tokens: Vec<PersonalAccessToken>,
+}
+
+#[get("/auth/tokens")]
+pub fn get_all(
+    database_connection: DbConnection,
+    user: User,
+) -> ResponseResult<GetAllPersonalAccessTokensResponse> {
+    let tokens = user
+        .get_all_personal_access_tokens(&database_connection)
+        .map_err(DB_ERROR_MAP_FN)?;
+
+    Ok(SuccessResponse::new(GetAllPersonalAccessTokensResponse {
+        tokens,
+    }))
+}
+
+#[delete("/auth/tokens/<personal_access_token_id>")]
+pub fn delete(
+    personal_access_token_id: i32,
+    database_connection: DbConnection,
+    user: User,
+) -> ResponseResult {
+    let personal_access_token = user
+        .get_personal_access_token_by_id(&database_connection, personal_access_token_id)
+        .ok_or_else(|| ErrorResponse::fail("Token Not Found".to_string(), Status::NotFound))?;
+
+    personal_access_token
+        .delete(&database_connection)
+        .map_err(DB_ERROR_MAP_FN)?;
+
+    Ok(SuccessResponse::new(()))
+}
@@ -1,17 +1,31 @@
-use crate::database::DbConnection;
+use crate::analyzer::cache::AnalysisCache;
+use crate::analyzer::{Analyzer, Analyzers};
+use crate::database::{DbConnection, Pool};
+use crate::difficulty::DifficultyWeights;
 use crate::field_validator::validate;
-use crate::frequency_list::JpFrequencyList;
+use crate::frequency_list::bands::FrequencyBands;
+use crate::frequency_list::jlpt::{JlptLevel, JlptList};
+use crate::frequency_list::{FrequencyList, FrequencyLists};
 use crate::models::mining_batch::MiningBatch;
 use crate::models::sentence::Sentence;
-use crate::models::user::{CommitSentencesError, User, UserSentenceEntry};
+use crate::models::user::{
+    CommitSentencesError, PendingSentenceOrder, PendingSentencesOptions, User, UserSentenceEntry,
+};
 use crate::models::word::Word;
+use crate::models::word_kanji::WordKanji;
 use crate::responses::{ErrorResponse, ResponseResult, SuccessResponse};
+use crate::routes::analyzer::{
+    analyze_uncached_sentences, annotate_sentences, cache_analyses, get_analyzer_for_user,
+    get_cached_analyses, get_database_connection, score_difficulty,
+};
+use crate::scope::{BatchesRead, BatchesWrite, ScopedUser, SentencesRead, SentencesWrite};
 use diesel::result::Error;
 use rocket::http::Status;
 use rocket::serde::json::Json;
 use rocket::serde::{Deserialize, Serialize};
 use rocket::State;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use validator::{Validate, ValidationError};
 
 const DB_ERROR_MAP_FN: fn(Error) -> ErrorResponse =
@@ -36,9 +50,12 @@ pub struct NewSentenceResponse {
 pub fn new(
     new_sentence_request: Json<NewSentenceRequest>,
     database_connection: DbConnection,
-    user: User,
-    frequency_list: &State<JpFrequencyList>,
+    user: ScopedUser<SentencesWrite>,
+    frequency_lists: &State<FrequencyLists>,
+    frequency_bands: &State<FrequencyBands>,
+    jlpt_list: &State<JlptList>,
 ) -> ResponseResult<NewSentenceResponse> {
+    let frequency_list = frequency_lists.get_for_user(&user);
     let new_sentence_data = validate(new_sentence_request)?;
 
     let dictionary_form = new_sentence_data.dictionary_form.trim().to_string();
@@ -62,8 +79,24 @@ pub fn new(
     let sentence_entry = Sentence::new(&database_connection, &user, &word_entry, &sentence)
         .map_err(DB_ERROR_MAP_FN)?;
 
+    let custom_frequency_list = user
+        .get_custom_frequency_list(&database_connection, &[dictionary_form])
+        .map_err(DB_ERROR_MAP_FN)?;
+    let frequency_list = custom_frequency_list.as_deref().unwrap_or(frequency_list);
+    let known_kanji =
+        WordKanji::get_known_kanji(&database_connection, &user).map_err(DB_ERROR_MAP_FN)?;
+
+    // Scored when the pending sentences are sorted by difficulty, against the words known then.
     Ok(SuccessResponse::new(NewSentenceResponse {
-        sentence: UserSentenceEntry::new(&word_entry, &sentence_entry, frequency_list),
+        sentence: UserSentenceEntry::new(
+            &word_entry,
+            &sentence_entry,
+            frequency_list,
+            frequency_bands,
+            jlpt_list,
+            &known_kanji,
+            None,
+        ),
     }))
 }
 
@@ -72,14 +105,125 @@ pub struct GetSentenceResponse {
     sentences: Vec<UserSentenceEntry>,
 }
 
-#[get("/sentences")]
-pub fn get(
-    database_connection: DbConnection,
-    user: User,
-    frequency_list: &State<JpFrequencyList>,
+/// Difficulty of each pending sentence text, analyzing the ones missing from the cache and
+/// annotating all of them in one pass.
+#[allow(clippy::too_many_arguments)]
+async fn score_pending_sentences(
+    database_pool: &Pool,
+    user: &User,
+    analyzer: &Arc<dyn Analyzer>,
+    analysis_cache: &AnalysisCache,
+    frequency_list: &dyn FrequencyList,
+    frequency_bands: &FrequencyBands,
+    jlpt_list: &JlptList,
+    difficulty_weights: &DifficultyWeights,
+) -> Result<HashMap<String, f64>, ErrorResponse> {
+    let configuration_key = analyzer.configuration_key();
+
+    let (sentences, cached_analyses) = {
+        let database_connection = get_database_connection(database_pool)?;
+        let sentences = user
+            .get_pending_sentence_texts(&database_connection)
+            .map_err(DB_ERROR_MAP_FN)?;
+        let cached_analyses = get_cached_analyses(
+            analysis_cache,
+            &database_connection,
+            &configuration_key,
+            &sentences,
+        )?;
+
+        (sentences, cached_analyses)
+    };
+    let analyses = analyze_uncached_sentences(analyzer, &sentences, cached_analyses).await?;
+    let database_connection = get_database_connection(database_pool)?;
+    cache_analyses(
+        analysis_cache,
+        &database_connection,
+        &configuration_key,
+        &sentences,
+        &analyses,
+    )?;
+
+    let sentences_morphemes = annotate_sentences(
+        &database_connection,
+        user,
+        frequency_list,
+        frequency_bands,
+        jlpt_list,
+        analyses
+            .into_iter()
+            .map(|(morphemes, _)| morphemes)
+            .collect(),
+    )?;
+
+    Ok(sentences
+        .into_iter()
+        .zip(sentences_morphemes)
+        .map(|(sentence, morphemes)| {
+            let difficulty = score_difficulty(&sentence, &morphemes, difficulty_weights);
+            (sentence, difficulty)
+        })
+        .collect())
+}
+
+#[allow(clippy::too_many_arguments)]
+#[get("/sentences?<jlpt_level>&<order>")]
+pub async fn get(
+    jlpt_level: Option<String>,
+    order: Option<String>,
+    database_pool: &State<Pool>,
+    user: ScopedUser<SentencesRead>,
+    analyzers: &State<Analyzers>,
+    analysis_cache: &State<AnalysisCache>,
+    frequency_lists: &State<FrequencyLists>,
+    frequency_bands: &State<FrequencyBands>,
+    jlpt_list: &State<JlptList>,
+    difficulty_weights: &State<DifficultyWeights>,
 ) -> ResponseResult<GetSentenceResponse> {
+    let analyzer = get_analyzer_for_user(analyzers, &user)?;
+    let frequency_list = frequency_lists.get_for_user(&user);
+    let jlpt_level = jlpt_level
+        .map(|jlpt_level| jlpt_level.parse::<JlptLevel>())
+        .transpose()
+        .map_err(|_| {
+            ErrorResponse::fail(
+                "Invalid JLPT Level".to_string(),
+                Status::UnprocessableEntity,
+            )
+        })?;
+    let order = order
+        .map(|order| order.parse::<PendingSentenceOrder>())
+        .transpose()
+        .map_err(|_| ErrorResponse::fail("Invalid Order".to_string(), Status::UnprocessableEntity))?
+        .unwrap_or_default();
+
+    let difficulties = match order {
+        PendingSentenceOrder::Difficulty => {
+            score_pending_sentences(
+                database_pool,
+                &user,
+                &analyzer,
+                analysis_cache,
+                frequency_list,
+                frequency_bands,
+                jlpt_list,
+                difficulty_weights,
+            )
+            .await?
+        }
+        PendingSentenceOrder::Mining => HashMap::new(),
+    };
+
+    let database_connection = get_database_connection(database_pool)?;
     let pending_sentences = user
-        .get_pending_sentences(&database_connection, frequency_list)
+        .get_pending_sentences(
+            &database_connection,
+            frequency_list,
+            frequency_bands,
+            jlpt_list,
+            &difficulties,
+            &PendingSentencesOptions { jlpt_level, order },
+        )
         .map_err(DB_ERROR_MAP_FN)?;
 
     Ok(SuccessResponse::new(GetSentenceResponse {
@@ -88,7 +232,11 @@ pub fn get(
 }
 
 #[delete("/sentences/<sentence_id>")]
-pub fn delete(sentence_id: i32, database_connection: DbConnection, user: User) -> ResponseResult {
+pub fn delete(
+    sentence_id: i32,
+    database_connection: DbConnection,
+    user: ScopedUser<SentencesWrite>,
+) -> ResponseResult {
     let pending_sentence = user
         .get_pending_sentence_by_id(&database_connection, sentence_id)
         .ok_or_else(|| {
@@ -125,7 +273,7 @@ pub struct NewBatchResponse {
 pub fn new_batch(
     new_batch_request: Json<NewBatchRequest>,
     database_connection: DbConnection,
-    user: User,
+    user: ScopedUser<BatchesWrite>,
 ) -> ResponseResult<NewBatchResponse> {
     let new_batch_data = validate(new_batch_request)?;
 
@@ -155,15 +303,24 @@ pub struct GetBatchResponse {
 pub fn get_batch(
     mining_batch_id: i32,
     database_connection: DbConnection,
-    user: User,
-    frequency_list: &State<JpFrequencyList>,
+    user: ScopedUser<BatchesRead>,
+    frequency_lists: &State<FrequencyLists>,
+    frequency_bands: &State<FrequencyBands>,
+    jlpt_list: &State<JlptList>,
 ) -> ResponseResult<GetBatchResponse> {
+    let frequency_list = frequency_lists.get_for_user(&user);
     let mining_batch = user
         .get_mining_batch_by_id(&database_connection, mining_batch_id)
         .ok_or_else(|| ErrorResponse::fail("Batch Not Found".to_string(), Status::NotFound))?;
 
     let sentences = mining_batch
-        .get_sentences(&database_connection, frequency_list)
+        .get_sentences(
+            &database_connection,
+            &user,
+            frequency_list,
+            frequency_bands,
+            jlpt_list,
+        )
         .map_err(DB_ERROR_MAP_FN)?;
 
     Ok(SuccessResponse::new(GetBatchResponse { sentences }))
@@ -177,7 +334,7 @@ pub struct GetAllBatchesResponse {
 #[get("/sentences/batches")]
 pub fn get_all_batches(
     database_connection: DbConnection,
-    user: User,
+    user: ScopedUser<BatchesRead>,
 ) -> ResponseResult<GetAllBatchesResponse> {
     let batches = user
         .get_all_mining_batches(&database_connection)
@@ -0,0 +1,41 @@
+use crate::database::DbConnection;
+use crate::models::session::Session;
+use crate::models::user::User;
+use crate::responses::{ErrorResponse, ResponseResult, SuccessResponse};
+use diesel::result::Error;
+use rocket::http::Status;
+use rocket::serde::Serialize;
+
+const DB_ERROR_MAP_FN: fn(Error) -> ErrorResponse =
+    |_| ErrorResponse::error("Unexpected Error".to_string(), Status::InternalServerError);
+
+#[derive(Serialize)]
+pub struct GetAllSessionsResponse {
+    pub sessions: Vec<Session>,
+}
+
+#[get("/auth/sessions")]
+pub fn get_all(
+    database_connection: DbConnection,
+    user: User,
+) -> ResponseResult<GetAllSessionsResponse> {
+    let sessions = user
+        .get_all_sessions(&database_connection)
+        .map_err(DB_ERROR_MAP_FN)?;
+
+    Ok(SuccessResponse::new(GetAllSessionsResponse { sessions }))
+}
+
+/// Logs a device out: both the refresh and access tokens of the session stop working.
+#[delete("/auth/sessions/<session_id>")]
+pub fn delete(session_id: i32, database_connection: DbConnection, user: User) -> ResponseResult {
+    let session = user
+        .get_session_by_id(&database_connection, session_id)
+        .ok_or_else(|| ErrorResponse::fail("Session Not Found".to_string(), Status::NotFound))?;
+
+    session
+        .delete(&database_connection)
+        .map_err(DB_ERROR_MAP_FN)?;
+
+    Ok(SuccessResponse::new(()))
+}
@@ -1,3 +1,44 @@
+table! {
+    analysis_cache_entries (configuration_key, sentence) {
+        configuration_key -> Text,
+        sentence -> Text,
+        morphemes -> Text,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    custom_frequency_list_entries (id) {
+        id -> Int4,
+        user_id -> Int4,
+        normalized_form -> Text,
+        dictionary_form -> Text,
+        reading -> Nullable<Text>,
+        frequency -> Int4,
+        language -> Text,
+    }
+}
+
+table! {
+    custom_frequency_lists (user_id, language) {
+        user_id -> Int4,
+        word_count -> Int4,
+        created_at -> Timestamptz,
+        language -> Text,
+    }
+}
+
+table! {
+    invites (id) {
+        id -> Int4,
+        code -> Text,
+        created_by -> Int4,
+        remaining_uses -> Int4,
+        expires_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
 table! {
     mining_batches (id) {
         id -> Int4,
@@ -7,6 +48,19 @@ table! {
     }
 }
 
+table! {
+    personal_access_tokens (id) {
+        id -> Int4,
+        user_id -> Int4,
+        name -> Text,
+        token_hash -> Text,
+        scopes -> Array<Text>,
+        expires_at -> Nullable<Timestamptz>,
+        last_used_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
 table! {
     sentences (id) {
         id -> Int4,
@@ -17,6 +71,20 @@ table! {
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
         mining_batch_id -> Nullable<Int4>,
+        language -> Text,
+    }
+}
+
+table! {
+    sessions (id) {
+        id -> Int4,
+        user_id -> Int4,
+        device_name -> Nullable<Text>,
+        user_agent -> Nullable<Text>,
+        last_ip -> Nullable<Text>,
+        created_at -> Timestamptz,
+        last_used_at -> Timestamptz,
+        refresh_token_id -> Nullable<Text>,
     }
 }
 
@@ -29,6 +97,8 @@ table! {
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
         token_generation -> Int4,
+        frequency_list -> Nullable<Text>,
+        language -> Text,
     }
 }
 
@@ -42,18 +112,40 @@ table! {
         is_mined -> Bool,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
+        language -> Text,
+    }
+}
+
+table! {
+    word_kanji (word_id, kanji) {
+        word_id -> Int4,
+        user_id -> Int4,
+        kanji -> Text,
     }
 }
 
+joinable!(custom_frequency_lists -> users (user_id));
+joinable!(invites -> users (created_by));
 joinable!(mining_batches -> users (user_id));
+joinable!(personal_access_tokens -> users (user_id));
 joinable!(sentences -> mining_batches (mining_batch_id));
 joinable!(sentences -> users (user_id));
 joinable!(sentences -> words (word_id));
+joinable!(sessions -> users (user_id));
+joinable!(word_kanji -> users (user_id));
+joinable!(word_kanji -> words (word_id));
 joinable!(words -> users (user_id));
 
 allow_tables_to_appear_in_same_query!(
+    analysis_cache_entries,
+    custom_frequency_list_entries,
+    custom_frequency_lists,
+    invites,
     mining_batches,
+    personal_access_tokens,
     sentences,
+    sessions,
     users,
+    word_kanji,
     words,
 );
@@ -0,0 +1,160 @@
+use crate::jwt::TokenError;
+use crate::models::user::{authenticate_request, User};
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::Pg;
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::Text;
+use rocket::request::{FromRequest, Outcome, Request};
+use rocket::serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+/// What a personal access token may be used for, stored and exchanged as its code.
+#[derive(
+    Serialize,
+    Deserialize,
+    AsExpression,
+    FromSqlRow,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+)]
+#[sql_type = "Text"]
+pub enum Scope {
+    #[serde(rename = "analyze")]
+    Analyze,
+    #[serde(rename = "sentences:read")]
+    SentencesRead,
+    #[serde(rename = "sentences:write")]
+    SentencesWrite,
+    #[serde(rename = "batches:read")]
+    BatchesRead,
+    #[serde(rename = "batches:write")]
+    BatchesWrite,
+    #[serde(rename = "frequency-lists:read")]
+    FrequencyListsRead,
+    #[serde(rename = "kanji:read")]
+    KanjiRead,
+}
+
+impl Scope {
+    pub const ALL: [Scope; 7] = [
+        Scope::Analyze,
+        Scope::SentencesRead,
+        Scope::SentencesWrite,
+        Scope::BatchesRead,
+        Scope::BatchesWrite,
+        Scope::FrequencyListsRead,
+        Scope::KanjiRead,
+    ];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Scope::Analyze => "analyze",
+            Scope::SentencesRead => "sentences:read",
+            Scope::SentencesWrite => "sentences:write",
+            Scope::BatchesRead => "batches:read",
+            Scope::BatchesWrite => "batches:write",
+            Scope::FrequencyListsRead => "frequency-lists:read",
+            Scope::KanjiRead => "kanji:read",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Scope::ALL
+            .iter()
+            .find(|scope| scope.code() == code)
+            .copied()
+    }
+}
+
+impl ToSql<Text, Pg> for Scope {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+        out.write_all(self.code().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for Scope {
+    fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+        let code = <String as FromSql<Text, Pg>>::from_sql(bytes)?;
+
+        Scope::from_code(&code).ok_or_else(|| format!("unknown scope \"{}\"", code).into())
+    }
+}
+
+/// A scope as a type, to name it in [`ScopedUser`].
+pub trait RouteScope: Send + Sync + 'static {
+    const SCOPE: Scope;
+}
+
+pub struct Analyze;
+
+impl RouteScope for Analyze {
+    const SCOPE: Scope = Scope::Analyze;
+}
+
+pub struct SentencesRead;
+
+impl RouteScope for SentencesRead {
+    const SCOPE: Scope = Scope::SentencesRead;
+}
+
+pub struct SentencesWrite;
+
+impl RouteScope for SentencesWrite {
+    const SCOPE: Scope = Scope::SentencesWrite;
+}
+
+pub struct BatchesRead;
+
+impl RouteScope for BatchesRead {
+    const SCOPE: Scope = Scope::BatchesRead;
+}
+
+pub struct BatchesWrite;
+
+impl RouteScope for BatchesWrite {
+    const SCOPE: Scope = Scope::BatchesWrite;
+}
+
+pub struct FrequencyListsRead;
+
+impl RouteScope for FrequencyListsRead {
+    const SCOPE: Scope = Scope::FrequencyListsRead;
+}
+
+pub struct KanjiRead;
+
+impl RouteScope for KanjiRead {
+    const SCOPE: Scope = Scope::KanjiRead;
+}
+
+/// Guard for the user of a route that personal access tokens granting `S` may call as well.
+/// Routes taking a plain `User` only accept JWTs.
+pub struct ScopedUser<S: RouteScope>(pub User, PhantomData<S>);
+
+#[rocket::async_trait]
+impl<'r, S: RouteScope> FromRequest<'r> for ScopedUser<S> {
+    type Error = TokenError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        authenticate_request(request, Some(S::SCOPE))
+            .await
+            .map(|user| ScopedUser(user, PhantomData))
+    }
+}
+
+impl<S: RouteScope> Deref for ScopedUser<S> {
+    type Target = User;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
@@ -1,6 +1,17 @@
+use chrono::{Duration, Utc};
 use common::*;
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl};
 use rocket::http::Status;
+use rocket::serde::json::Value;
+use sentence_base::analyzer::cache::AnalysisCache;
+use sentence_base::analyzer::Morpheme;
 use sentence_base::jwt::TokenType;
+use sentence_base::models::sentence::Sentence;
+use sentence_base::models::user::User;
+use sentence_base::models::word::Word;
+use sentence_base::schema::analysis_cache_entries;
+use sentence_base::schema::words::dsl::words as dsl_words;
+use sentence_base::schema::words::{id as schema_words_id, is_mined as schema_words_is_mined};
 use serde_json::json;
 
 mod common;
@@ -112,3 +123,711 @@ fn analyze_should_morphemalize() {
         index += 1;
     }
 }
+
+#[test]
+fn analyze_should_return_part_of_speech_data() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_post_request_with_json_and_auth(
+        &client,
+        "/analyze",
+        &access_token,
+        json!({
+            "sentence": SENTENCES[0]
+        }),
+    );
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+
+    let morphemes = json
+        .get("data")
+        .unwrap()
+        .get("morphemes")
+        .expect("should include 'morphemes' field")
+        .as_array()
+        .expect("'morphemes' should be an array");
+
+    assert_eq!(
+        morphemes.to_vec(),
+        vec![
+            json!({
+                "morpheme": "これ",
+                "dictionary_form": "これ",
+                "reading": "コレ",
+                "part_of_speech": "noun",
+                "part_of_speech_subcategories": ["代名詞", "一般"],
+                "conjugation_type": null,
+                "conjugation_form": null,
+                "furigana": [{ "text": "これ" }],
+                "offsets": {
+                    "utf16": { "start": 0, "end": 2 },
+                    "scalar": { "start": 0, "end": 2 },
+                },
+                "is_known": false,
+                "is_mined": false,
+                "mining_frequency": null,
+                "dictionary_frequency": 42,
+                "dictionary_frequency_band": "top_1k",
+                "jlpt_level": "N5",
+            }),
+            json!({
+                "morpheme": "は",
+                "dictionary_form": "は",
+                "reading": "ハ",
+                "part_of_speech": "particle",
+                "part_of_speech_subcategories": ["係助詞"],
+                "conjugation_type": null,
+                "conjugation_form": null,
+                "furigana": [{ "text": "は" }],
+                "offsets": {
+                    "utf16": { "start": 2, "end": 3 },
+                    "scalar": { "start": 2, "end": 3 },
+                },
+                "is_known": false,
+                "is_mined": false,
+                "mining_frequency": null,
+                "dictionary_frequency": 1,
+                "dictionary_frequency_band": "top_1k",
+                "jlpt_level": null,
+            }),
+            json!({
+                "morpheme": "ペン",
+                "dictionary_form": "ペン",
+                "reading": "ペン",
+                "part_of_speech": "noun",
+                "part_of_speech_subcategories": ["一般"],
+                "conjugation_type": null,
+                "conjugation_form": null,
+                "furigana": [{ "text": "ペン" }],
+                "offsets": {
+                    "utf16": { "start": 3, "end": 5 },
+                    "scalar": { "start": 3, "end": 5 },
+                },
+                "is_known": false,
+                "is_mined": false,
+                "mining_frequency": null,
+                "dictionary_frequency": 2847,
+                "dictionary_frequency_band": "1k_5k",
+                "jlpt_level": "N5",
+            }),
+            json!({
+                "morpheme": "です",
+                "dictionary_form": "です",
+                "reading": "デス",
+                "part_of_speech": "auxiliary_verb",
+                "part_of_speech_subcategories": [],
+                "conjugation_type": "特殊・デス",
+                "conjugation_form": "基本形",
+                "furigana": [{ "text": "です" }],
+                "offsets": {
+                    "utf16": { "start": 5, "end": 7 },
+                    "scalar": { "start": 5, "end": 7 },
+                },
+                "is_known": false,
+                "is_mined": false,
+                "mining_frequency": null,
+                "dictionary_frequency": 16,
+                "dictionary_frequency_band": "top_1k",
+                "jlpt_level": null,
+            }),
+            json!({
+                "morpheme": "。",
+                "dictionary_form": "。",
+                "reading": "。",
+                "part_of_speech": "symbol",
+                "part_of_speech_subcategories": ["句点"],
+                "conjugation_type": null,
+                "conjugation_form": null,
+                "furigana": [{ "text": "。" }],
+                "offsets": {
+                    "utf16": { "start": 7, "end": 8 },
+                    "scalar": { "start": 7, "end": 8 },
+                },
+                "is_known": false,
+                "is_mined": false,
+                "mining_frequency": null,
+                "dictionary_frequency": 102845,
+                "dictionary_frequency_band": "rare",
+                "jlpt_level": null,
+            }),
+        ]
+    );
+}
+
+#[test]
+fn analyze_should_include_raw_features_when_requested() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_post_request_with_json_and_auth(
+        &client,
+        "/analyze",
+        &access_token,
+        json!({
+            "sentence": SENTENCES[0],
+            "include_features": true
+        }),
+    );
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+
+    let features = json
+        .get("data")
+        .unwrap()
+        .get("morphemes")
+        .expect("should include 'morphemes' field")
+        .get(0)
+        .expect("index 0 should exist")
+        .get("features")
+        .expect("should include 'features' field");
+
+    assert_eq!(
+        features,
+        &json!([
+            "名詞",
+            "代名詞",
+            "一般",
+            "*",
+            "*",
+            "*",
+            "これ",
+            "コレ",
+            "コレ"
+        ])
+    );
+}
+
+#[test]
+fn analyze_should_return_furigana() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_post_request_with_json_and_auth(
+        &client,
+        "/analyze",
+        &access_token,
+        json!({
+            "sentence": "私は食べる。"
+        }),
+    );
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+
+    let data = json.get("data").unwrap();
+    let furigana = data
+        .get("morphemes")
+        .expect("should include 'morphemes' field")
+        .as_array()
+        .expect("'morphemes' should be an array")
+        .iter()
+        .map(|morpheme| {
+            morpheme
+                .get("furigana")
+                .expect("should include 'furigana' field")
+                .clone()
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        furigana,
+        vec![
+            json!([{ "text": "私", "reading": "わたし" }]),
+            json!([{ "text": "は" }]),
+            json!([{ "text": "食", "reading": "た" }, { "text": "べる" }]),
+            json!([{ "text": "。" }]),
+        ]
+    );
+    assert_eq!(
+        data.get("furigana"),
+        Some(&json!({
+            "anki": "私[わたし]は 食[た]べる。",
+            "html": "<ruby>私<rt>わたし</rt></ruby>は<ruby>食<rt>た</rt></ruby>べる。"
+        }))
+    );
+}
+
+#[test]
+fn analyze_should_return_offsets() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_post_request_with_json_and_auth(
+        &client,
+        "/analyze",
+        &access_token,
+        json!({
+            "sentence": "🍣は猫と猫"
+        }),
+    );
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+
+    let offsets = json
+        .get("data")
+        .unwrap()
+        .get("morphemes")
+        .expect("should include 'morphemes' field")
+        .as_array()
+        .expect("'morphemes' should be an array")
+        .iter()
+        .map(|morpheme| {
+            morpheme
+                .get("offsets")
+                .expect("should include 'offsets' field")
+                .clone()
+        })
+        .collect::<Vec<_>>();
+
+    let expected_offsets: [((usize, usize), (usize, usize)); 5] = [
+        ((0, 2), (0, 1)),
+        ((2, 3), (1, 2)),
+        ((3, 4), (2, 3)),
+        ((4, 5), (3, 4)),
+        ((5, 6), (4, 5)),
+    ];
+
+    assert_eq!(
+        offsets,
+        expected_offsets
+            .iter()
+            .map(
+                |((utf16_start, utf16_end), (scalar_start, scalar_end))| json!({
+                    "utf16": { "start": utf16_start, "end": utf16_end },
+                    "scalar": { "start": scalar_start, "end": scalar_end },
+                })
+            )
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn analyze_should_annotate_vocabulary_state() {
+    let (client, user, database_connection) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    Word::new_or_increase_frequency(&database_connection, &user, "ペン", "ペン")
+        .expect("word should be created");
+
+    let response = send_post_request_with_json_and_auth(
+        &client,
+        "/analyze",
+        &access_token,
+        json!({
+            "sentence": SENTENCES[0]
+        }),
+    );
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+
+    let data = json.get("data").unwrap();
+    let vocabulary_states = data
+        .get("morphemes")
+        .expect("should include 'morphemes' field")
+        .as_array()
+        .expect("'morphemes' should be an array")
+        .iter()
+        .map(|morpheme| {
+            (
+                morpheme.get("morpheme").unwrap().as_str().unwrap(),
+                morpheme.get("is_known").unwrap().as_bool().unwrap(),
+                morpheme.get("is_mined").unwrap().as_bool().unwrap(),
+                morpheme.get("mining_frequency").unwrap().as_i64(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        vocabulary_states,
+        vec![
+            ("これ", false, false, None),
+            ("は", false, false, None),
+            ("ペン", true, false, Some(1)),
+            ("です", false, false, None),
+            ("。", false, false, None),
+        ]
+    );
+    assert_eq!(data.get("unknown_word_count"), Some(&json!(1)));
+    assert!(data["difficulty"].as_f64().is_some());
+}
+
+fn add_mined_word(
+    database_connection: &PgConnection,
+    user: &User,
+    dictionary_form: &str,
+    reading: &str,
+) {
+    let word = Word::new_or_increase_frequency(database_connection, user, dictionary_form, reading)
+        .expect("word should be created");
+
+    diesel::update(dsl_words.filter(schema_words_id.eq(word.id)))
+        .set(schema_words_is_mined.eq(true))
+        .execute(database_connection)
+        .expect("word should be marked as mined");
+}
+
+#[test]
+fn evaluate_should_require_auth() {
+    let (client, _) = create_client();
+
+    let response = send_post_request_with_json(
+        &client,
+        "/analyze/evaluate",
+        json!({
+            "sentences": SENTENCES
+        }),
+    );
+    assert_eq!(response.status(), Status::Unauthorized);
+    let json = response_to_json(response);
+    assert_fail(&json, "No Token Provided");
+}
+
+#[test]
+fn evaluate_should_validate() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    for sentences in [json!([]), json!(["これはペンです。", " "])] {
+        let response = send_post_request_with_json_and_auth(
+            &client,
+            "/analyze/evaluate",
+            &access_token,
+            json!({
+                "sentences": sentences
+            }),
+        );
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+        let json = response_to_json(response);
+        assert_fail(&json, "Validation Error");
+        assert_fail_reasons_validation_fields(&json, vec!["sentences".to_string()]);
+    }
+}
+
+#[test]
+fn evaluate_should_detect_i_plus_one_sentences() {
+    let (client, user, database_connection) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    add_mined_word(&database_connection, &user, "これ", "コレ");
+
+    let response = send_post_request_with_json_and_auth(
+        &client,
+        "/analyze/evaluate",
+        &access_token,
+        json!({
+            "sentences": ["これはペンです。", "猫と犬。"]
+        }),
+    );
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+
+    let evaluations = json
+        .get("data")
+        .unwrap()
+        .get("sentences")
+        .expect("should include 'sentences' field")
+        .as_array()
+        .expect("'sentences' should be an array");
+
+    assert_eq!(evaluations.len(), 2);
+
+    assert_eq!(evaluations[0].get("is_i_plus_one"), Some(&json!(true)));
+    assert_eq!(
+        evaluations[0].get("target_word"),
+        Some(&json!({ "dictionary_form": "ペン", "reading": "ペン" }))
+    );
+    assert_eq!(
+        evaluations[0].get("unknown_words"),
+        Some(&json!([{
+            "dictionary_form": "ペン",
+            "reading": "ペン",
+            "dictionary_frequency": 2847,
+            "jlpt_level": "N5",
+        }]))
+    );
+    assert_eq!(evaluations[0].get("pending_sentence"), Some(&json!(null)));
+
+    assert_eq!(evaluations[1].get("is_i_plus_one"), Some(&json!(false)));
+    assert_eq!(evaluations[1].get("target_word"), Some(&json!(null)));
+    assert_eq!(
+        evaluations[1]
+            .get("unknown_words")
+            .and_then(|unknown_words| unknown_words.as_array())
+            .map(|unknown_words| unknown_words.len()),
+        Some(2)
+    );
+}
+
+#[test]
+fn evaluate_should_add_i_plus_one_sentences_to_pending() {
+    let (client, user, database_connection) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    add_mined_word(&database_connection, &user, "これ", "コレ");
+
+    let response = send_post_request_with_json_and_auth(
+        &client,
+        "/analyze/evaluate",
+        &access_token,
+        json!({
+            "sentences": ["これはペンです。", "猫と犬。"],
+            "add_to_pending": true
+        }),
+    );
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+
+    let pending_sentence = json
+        .get("data")
+        .unwrap()
+        .get("sentences")
+        .and_then(|sentences| sentences.get(0))
+        .and_then(|evaluation| evaluation.get("pending_sentence"))
+        .expect("should include 'pending_sentence' field");
+
+    assert_eq!(
+        pending_sentence.get("sentence"),
+        Some(&json!("これはペンです。"))
+    );
+    assert_eq!(
+        pending_sentence.get("dictionary_form"),
+        Some(&json!("ペン"))
+    );
+    assert_eq!(pending_sentence.get("reading"), Some(&json!("ペン")));
+
+    let sentences: Vec<Sentence> = sentence_base::schema::sentences::table
+        .load(&database_connection)
+        .expect("should load sentences");
+
+    assert_eq!(sentences.len(), 1);
+    assert_eq!(sentences[0].sentence, "これはペンです。");
+    assert!(sentences[0].is_pending);
+}
+
+#[test]
+fn analyze_text_should_require_auth() {
+    let (client, _) = create_client();
+
+    let response = send_post_request_with_json(
+        &client,
+        "/analyze/text",
+        json!({
+            "text": SENTENCES[0]
+        }),
+    );
+    assert_eq!(response.status(), Status::Unauthorized);
+    let json = response_to_json(response);
+    assert_fail(&json, "No Token Provided");
+}
+
+#[test]
+fn analyze_text_should_validate() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_post_request_with_json_and_auth(
+        &client,
+        "/analyze/text",
+        &access_token,
+        json!({
+            "text": ""
+        }),
+    );
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    let json = response_to_json(response);
+    assert_fail(&json, "Validation Error");
+    assert_fail_reasons_validation_fields(&json, vec!["text".to_string()]);
+}
+
+fn collect_text_sentences(json: &Value) -> Vec<(String, u64)> {
+    json.get("data")
+        .unwrap()
+        .get("sentences")
+        .expect("should include 'sentences' field")
+        .as_array()
+        .expect("'sentences' should be an array")
+        .iter()
+        .map(|sentence| {
+            (
+                sentence
+                    .get("sentence")
+                    .unwrap()
+                    .as_str()
+                    .unwrap()
+                    .to_string(),
+                sentence
+                    .get("unknown_word_count")
+                    .unwrap()
+                    .as_u64()
+                    .unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn analyze_text_should_split_and_count_unknown_words() {
+    let (client, user, database_connection) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    Word::new_or_increase_frequency(&database_connection, &user, "これ", "コレ")
+        .expect("word should be created");
+
+    let response = send_post_request_with_json_and_auth(
+        &client,
+        "/analyze/text",
+        &access_token,
+        json!({
+            "text": "猫と犬。\nこれはペンです。"
+        }),
+    );
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+
+    assert_eq!(
+        collect_text_sentences(&json),
+        vec![
+            ("猫と犬。".to_string(), 2),
+            ("これはペンです。".to_string(), 1)
+        ]
+    );
+}
+
+#[test]
+fn analyze_text_should_order_by_mining_candidates() {
+    let (client, user, database_connection) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    Word::new_or_increase_frequency(&database_connection, &user, "これ", "コレ")
+        .expect("word should be created");
+
+    let response = send_post_request_with_json_and_auth(
+        &client,
+        "/analyze/text",
+        &access_token,
+        json!({
+            "text": "猫と犬。これは猫です。これはペンです。",
+            "order": "candidates"
+        }),
+    );
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+
+    assert_eq!(
+        collect_text_sentences(&json),
+        vec![
+            ("これは猫です。".to_string(), 1),
+            ("これはペンです。".to_string(), 1),
+            ("猫と犬。".to_string(), 2),
+        ]
+    );
+}
+
+#[test]
+fn analyze_should_report_cache_status() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    for expected_cache_status in ["miss", "hit"] {
+        let response = send_post_request_with_json_and_auth(
+            &client,
+            "/analyze",
+            &access_token,
+            json!({
+                "sentence": SENTENCES[0]
+            }),
+        );
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(
+            response.headers().get_one("X-Analysis-Cache"),
+            Some(expected_cache_status)
+        );
+    }
+
+    let response = send_get_request_with_auth(&client, "/analyze/cache", &access_token);
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+
+    let data = json.get("data").unwrap();
+    assert_eq!(data.get("hits").unwrap().as_u64(), Some(1));
+    assert_eq!(data.get("misses").unwrap().as_u64(), Some(1));
+    assert_eq!(data.get("size").unwrap().as_u64(), Some(1));
+}
+
+#[test]
+fn persistent_analysis_cache_should_look_up_sentences_and_prune_old_entries() {
+    let database_url = prepare_new_database();
+    let database_connection = create_database_connection(&database_url);
+    let analysis_cache = AnalysisCache::new(0, true, Some(Duration::days(1)));
+    let no_morphemes: &[Morpheme] = &[];
+
+    analysis_cache
+        .insert_all(
+            &database_connection,
+            "test",
+            &[("古い。", no_morphemes), ("新しい。", no_morphemes)],
+        )
+        .expect("analyses should be cached");
+    diesel::update(
+        analysis_cache_entries::table.filter(analysis_cache_entries::sentence.eq("古い。")),
+    )
+    .set(analysis_cache_entries::created_at.eq(Utc::now() - Duration::days(2)))
+    .execute(&database_connection)
+    .expect("entry should be updated");
+
+    let sentences = ["古い。", "新しい。", "未知。"].map(String::from);
+    let analyses = analysis_cache
+        .get_all(&database_connection, "test", &sentences)
+        .expect("analyses should be looked up");
+    assert_eq!(
+        analyses.iter().map(Option::is_some).collect::<Vec<bool>>(),
+        vec![true, true, false]
+    );
+
+    analysis_cache
+        .insert_all(&database_connection, "test", &[("未知。", no_morphemes)])
+        .expect("analyses should be cached");
+    let analyses = analysis_cache
+        .get_all(&database_connection, "other", &sentences)
+        .expect("analyses should be looked up");
+    assert!(analyses.iter().all(Option::is_none));
+    let analyses = analysis_cache
+        .get_all(&database_connection, "test", &sentences)
+        .expect("analyses should be looked up");
+    assert_eq!(
+        analyses.iter().map(Option::is_some).collect::<Vec<bool>>(),
+        vec![false, true, true]
+    );
+}
+
+#[test]
+fn analysis_cache_statistics_should_require_auth() {
+    let (client, _) = create_client();
+
+    let response = send_get_request(&client, "/analyze/cache");
+    assert_eq!(response.status(), Status::Unauthorized);
+    let json = response_to_json(response);
+    assert_fail(&json, "No Token Provided");
+}
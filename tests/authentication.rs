@@ -6,14 +6,18 @@ use rocket::local::blocking::{Client, LocalResponse};
 use sentence_base::helpers::{get_access_token_expiry_time, get_refresh_token_expiry_time};
 use sentence_base::jwt::{get_current_timestamp, get_jwt_secret_hmac, TokenClaims, TokenType};
 use sentence_base::models::user::User;
-use serde_json::json;
+use sentence_base::registration::RegistrationMode;
+use serde_json::{json, Value};
 
 mod common;
 
+fn create_client_with_open_registration() -> (Client, String) {
+    create_client_with_registration_mode(RegistrationMode::Open)
+}
+
 #[test]
-#[ignore]
 fn register_should_validate() {
-    let (client, _) = create_client();
+    let (client, _) = create_client_with_open_registration();
     let response = send_post_request_with_json(
         &client,
         "/auth/register",
@@ -40,9 +44,26 @@ fn register_should_validate() {
 }
 
 #[test]
-#[ignore]
+fn register_should_be_rejected_when_closed() {
+    let (client, _) = create_client_with_registration_mode(RegistrationMode::Closed);
+    let response = send_post_request_with_json(
+        &client,
+        "/auth/register",
+        json!({
+            "username": TEST_USERNAME,
+            "email": TEST_EMAIL,
+            "password": TEST_PASSWORD
+        }),
+    );
+
+    assert_eq!(response.status(), Status::Forbidden);
+    let json = response_to_json(response);
+    assert_fail(&json, "Registration Closed");
+}
+
+#[test]
 fn register_should_add_new_user() {
-    let (client, database_url) = create_client();
+    let (client, database_url) = create_client_with_open_registration();
     let response = send_post_request_with_json(
         &client,
         "/auth/register",
@@ -85,9 +106,8 @@ fn register_should_add_new_user() {
 }
 
 #[test]
-#[ignore]
 fn register_should_fail_on_duplicate_data() {
-    let (client, _) = create_client();
+    let (client, _) = create_client_with_open_registration();
     let registration_response = send_post_request_with_json(
         &client,
         "/auth/register",
@@ -135,9 +155,8 @@ fn register_should_fail_on_duplicate_data() {
 }
 
 #[test]
-#[ignore]
 fn register_should_fail_on_duplicate_data_in_different_case() {
-    let (client, _) = create_client();
+    let (client, _) = create_client_with_open_registration();
     let registration_response = send_post_request_with_json(
         &client,
         "/auth/register",
@@ -311,6 +330,8 @@ fn me_should_reject_future_iat_token() {
         sub: 0,
         gen: 0,
         typ: TokenType::Access,
+        sid: None,
+        jti: None,
     });
     let response = send_get_request_with_auth(&client, "/auth/me", &token);
     assert_eq!(response.status(), Status::Unauthorized);
@@ -329,6 +350,8 @@ fn me_should_reject_expired_token() {
         sub: 0,
         gen: 0,
         typ: TokenType::Access,
+        sid: None,
+        jti: None,
     });
     let response = send_get_request_with_auth(&client, "/auth/me", &token);
     assert_eq!(response.status(), Status::Unauthorized);
@@ -347,6 +370,8 @@ fn me_should_reject_invalid_subject() {
         sub: 0,
         gen: 0,
         typ: TokenType::Access,
+        sid: None,
+        jti: None,
     });
     let response = send_get_request_with_auth(&client, "/auth/me", &token);
     assert_eq!(response.status(), Status::Unauthorized);
@@ -365,6 +390,8 @@ fn me_should_reject_invalid_type() {
         sub: 0,
         gen: 0,
         typ: TokenType::Refresh,
+        sid: None,
+        jti: None,
     });
     let response = send_get_request_with_auth(&client, "/auth/me", &token);
     assert_eq!(response.status(), Status::Unauthorized);
@@ -446,6 +473,8 @@ fn refresh_should_reject_future_iat_token() {
         sub: 0,
         gen: 0,
         typ: TokenType::Refresh,
+        sid: None,
+        jti: None,
     });
     let response = send_refresh_request(&client, &token);
     assert_eq!(response.status(), Status::Unauthorized);
@@ -464,6 +493,8 @@ fn refresh_should_reject_expired_token() {
         sub: 0,
         gen: 0,
         typ: TokenType::Refresh,
+        sid: None,
+        jti: None,
     });
     let response = send_refresh_request(&client, &token);
     assert_eq!(response.status(), Status::Unauthorized);
@@ -482,6 +513,8 @@ fn refresh_should_reject_invalid_subject() {
         sub: 0,
         gen: 0,
         typ: TokenType::Refresh,
+        sid: None,
+        jti: None,
     });
     let response = send_refresh_request(&client, &token);
     assert_eq!(response.status(), Status::Unauthorized);
@@ -500,6 +533,8 @@ fn refresh_should_reject_invalid_type() {
         sub: 0,
         gen: 0,
         typ: TokenType::Access,
+        sid: None,
+        jti: None,
     });
     let response = send_refresh_request(&client, &token);
     assert_eq!(response.status(), Status::Unauthorized);
@@ -509,10 +544,9 @@ fn refresh_should_reject_invalid_type() {
 
 #[test]
 fn refresh_should_resolve_with_proper_token() {
-    let (client, user, _) =
-        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let (client, _, _) = create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
 
-    let token = generate_jwt_token_for_user(&user, TokenType::Refresh);
+    let (_, token) = login(&client);
     let response = send_refresh_request(&client, &token);
     assert_eq!(response.status(), Status::Ok);
     let json = response_to_json(response);
@@ -538,8 +572,7 @@ fn refresh_should_resolve_with_proper_token() {
 fn should_respect_token_generation() {
     let (client, mut user, database_connection) =
         create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
-    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
-    let refresh_token = generate_jwt_token_for_user(&user, TokenType::Refresh);
+    let (access_token, refresh_token) = login(&client);
 
     let first_me_response = send_get_request_with_auth(&client, "/auth/me", &access_token);
     assert_eq!(first_me_response.status(), Status::Ok);
@@ -559,6 +592,156 @@ fn should_respect_token_generation() {
     assert_fail(&second_refresh_response_json, "Revoked Token Provided");
 }
 
+#[test]
+fn logout_all_should_require_auth() {
+    let (client, _) = create_client();
+
+    let response = send_post_request_with_json(&client, "/auth/logout-all", json!({}));
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[test]
+fn logout_all_should_revoke_tokens_and_return_a_new_pair() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+    let refresh_token = generate_jwt_token_for_user(&user, TokenType::Refresh);
+
+    let response =
+        send_post_request_with_json_and_auth(&client, "/auth/logout-all", &access_token, json!({}));
+    assert_eq!(response.status(), Status::Ok);
+    let (new_access_token, new_refresh_token) = get_token_pair(response_to_json(response));
+
+    let response = send_get_request_with_auth(&client, "/auth/me", &access_token);
+    assert_fail(&response_to_json(response), "Revoked Token Provided");
+    let response = send_refresh_request(&client, &refresh_token);
+    assert_fail(&response_to_json(response), "Revoked Token Provided");
+
+    let response = send_get_request_with_auth(&client, "/auth/me", &new_access_token);
+    assert_eq!(response.status(), Status::Ok);
+    let response = send_refresh_request(&client, &new_refresh_token);
+    assert_eq!(response.status(), Status::Ok);
+}
+
+#[test]
+fn change_password_should_validate() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_put_request_with_json_and_auth(
+        &client,
+        "/auth/password",
+        &access_token,
+        json!({
+            "current_password": "",
+            "new_password": "short",
+        }),
+    );
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    let json = response_to_json(response);
+    assert_fail(&json, "Validation Error");
+    assert_fail_reasons_validation_fields(
+        &json,
+        vec!["current_password".to_string(), "new_password".to_string()],
+    );
+}
+
+#[test]
+fn change_password_should_reject_wrong_current_password() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_put_request_with_json_and_auth(
+        &client,
+        "/auth/password",
+        &access_token,
+        json!({
+            "current_password": "wrong password",
+            "new_password": "new password",
+        }),
+    );
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_fail(&response_to_json(response), "Invalid Credentials");
+
+    let response = send_get_request_with_auth(&client, "/auth/me", &access_token);
+    assert_eq!(response.status(), Status::Ok);
+}
+
+#[test]
+fn change_password_should_replace_password_and_revoke_tokens() {
+    let (client, user, database_connection) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+    let new_password = "new password";
+
+    let response = send_put_request_with_json_and_auth(
+        &client,
+        "/auth/password",
+        &access_token,
+        json!({
+            "current_password": TEST_PASSWORD,
+            "new_password": new_password,
+        }),
+    );
+    assert_eq!(response.status(), Status::Ok);
+    let (new_access_token, _) = get_token_pair(response_to_json(response));
+
+    let response = send_get_request_with_auth(&client, "/auth/me", &access_token);
+    assert_fail(&response_to_json(response), "Revoked Token Provided");
+    let response = send_get_request_with_auth(&client, "/auth/me", &new_access_token);
+    assert_eq!(response.status(), Status::Ok);
+
+    let user = User::find_by_id(&database_connection, user.id).expect("user should exist");
+    verify(new_password, &user.hash).expect("password should be hashed");
+    assert!(!user.verify_password(TEST_PASSWORD));
+
+    for (password, status) in [
+        (TEST_PASSWORD, Status::Unauthorized),
+        (new_password, Status::Ok),
+    ] {
+        let response = send_post_request_with_json(
+            &client,
+            "/auth/login",
+            json!({
+                "email": TEST_EMAIL,
+                "password": password
+            }),
+        );
+        assert_eq!(response.status(), status);
+    }
+}
+
+fn login(client: &Client) -> (String, String) {
+    let response = send_post_request_with_json(
+        client,
+        "/auth/login",
+        json!({
+            "email": TEST_EMAIL,
+            "password": TEST_PASSWORD
+        }),
+    );
+    assert_eq!(response.status(), Status::Ok);
+
+    get_token_pair(response_to_json(response))
+}
+
+fn get_token_pair(json: Value) -> (String, String) {
+    assert_success(&json);
+    let access_token = json["data"]["access_token"]
+        .as_str()
+        .expect("'access_token' should be a string");
+    let refresh_token = json["data"]["refresh_token"]
+        .as_str()
+        .expect("'refresh_token' should be a string");
+
+    assert_jwt_token(access_token, TokenType::Access);
+    assert_jwt_token(refresh_token, TokenType::Refresh);
+
+    (access_token.to_string(), refresh_token.to_string())
+}
+
 fn send_refresh_request<'a>(client: &'a Client, token: &'a String) -> LocalResponse<'a> {
     send_post_request_with_json(
         &client,
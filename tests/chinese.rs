@@ -0,0 +1,157 @@
+use sentence_base::analyzer::pinyin::{numbered_pinyin_to_tone_marks, PinyinDictionary};
+use sentence_base::frequency_list::chinese::{normalize_pinyin, ZhFrequencyList};
+use sentence_base::frequency_list::{FrequencyList, FrequencyLookup, FrequencyMatch};
+
+const CEDICT: &str = "# CC-CEDICT
+% sample
+中國 中国 [Zhong1 guo2] /China/
+北京 北京 [Bei3 jing1] /Beijing/
+西安 西安 [Xi1 an1] /Xi'an/
+綠 绿 [lu:4] /green/
+我 我 [wo3] /I; me/
+是 是 [shi4] /is; are/
+學生 学生 [xue2 sheng5] /student/
+學 学 [xue2] /to learn/
+生 生 [sheng1] /to be born/
+";
+
+#[test]
+fn numbered_pinyin_should_convert_to_tone_marks() {
+    assert_eq!(numbered_pinyin_to_tone_marks("Zhong1 guo2"), "Zhōngguó");
+    assert_eq!(numbered_pinyin_to_tone_marks("xue2 sheng5"), "xuésheng");
+    assert_eq!(numbered_pinyin_to_tone_marks("lu:4"), "lǜ");
+    assert_eq!(numbered_pinyin_to_tone_marks("gou3"), "gǒu");
+    assert_eq!(numbered_pinyin_to_tone_marks("gui4"), "guì");
+    assert_eq!(numbered_pinyin_to_tone_marks("Xi1 an1"), "Xī'ān");
+    assert_eq!(numbered_pinyin_to_tone_marks("T xu4"), "Txù");
+}
+
+#[test]
+fn pinyin_dictionary_should_read_cedict() {
+    let pinyin_dictionary = PinyinDictionary::parse(CEDICT.replace("% sample\n", "").as_bytes())
+        .expect("dictionary should parse");
+
+    assert_eq!(
+        pinyin_dictionary.get_reading("中国"),
+        Some("Zhōngguó".to_string())
+    );
+    assert_eq!(
+        pinyin_dictionary.get_reading("中國"),
+        Some("Zhōngguó".to_string())
+    );
+    assert_eq!(
+        pinyin_dictionary.get_reading("学生"),
+        Some("xuésheng".to_string())
+    );
+    assert_eq!(
+        pinyin_dictionary.get_reading("我是"),
+        Some("wǒshì".to_string())
+    );
+    assert_eq!(pinyin_dictionary.get_reading("猫"), None);
+}
+
+#[test]
+fn pinyin_dictionary_should_reject_malformed_lines() {
+    assert!(PinyinDictionary::parse(CEDICT.as_bytes()).is_err());
+}
+
+#[test]
+fn pinyin_should_normalize_tones_and_spacing() {
+    assert_eq!(normalize_pinyin("Běijīng"), "beijing");
+    assert_eq!(normalize_pinyin("bei3 jing1"), "beijing");
+    assert_eq!(normalize_pinyin("Xī'ān"), "xian");
+    assert_eq!(normalize_pinyin("lv4"), "lü");
+    assert_eq!(normalize_pinyin("lu:4"), "lü");
+}
+
+#[test]
+fn chinese_frequency_list_should_rank_words() {
+    let frequency_list = ZhFrequencyList::from_json(
+        r#"["的", ["是"], ["学生", "xuésheng"], ["行", "háng"], ["行", "xíng"]]"#,
+    )
+    .expect("frequency list should parse");
+
+    assert_eq!(frequency_list.len(), 5);
+    assert_eq!(
+        frequency_list.lookup_dictionary_form("的"),
+        FrequencyLookup {
+            frequency: 0,
+            match_type: FrequencyMatch::DictionaryForm
+        }
+    );
+    assert_eq!(
+        frequency_list.lookup("学生", "xuésheng"),
+        FrequencyLookup {
+            frequency: 2,
+            match_type: FrequencyMatch::Exact
+        }
+    );
+    assert_eq!(
+        frequency_list.lookup("行", "xing2"),
+        FrequencyLookup {
+            frequency: 4,
+            match_type: FrequencyMatch::Normalized
+        }
+    );
+    assert_eq!(
+        frequency_list.lookup("猫", "māo"),
+        FrequencyLookup {
+            frequency: 6,
+            match_type: FrequencyMatch::NotFound
+        }
+    );
+}
+
+#[test]
+fn chinese_frequency_list_should_reject_malformed_entries() {
+    assert!(ZhFrequencyList::from_json(r#"[["的", 1]]"#).is_err());
+    assert!(ZhFrequencyList::from_json(r#"{"的": "de"}"#).is_err());
+}
+
+#[test]
+fn empty_chinese_frequency_list_should_rank_every_word_last() {
+    let frequency_list = ZhFrequencyList::empty();
+
+    assert!(frequency_list.is_empty());
+    assert_eq!(
+        frequency_list.lookup("的", "de"),
+        FrequencyLookup {
+            frequency: 1,
+            match_type: FrequencyMatch::NotFound
+        }
+    );
+}
+
+#[cfg(feature = "jieba")]
+#[test]
+fn jieba_analyzer_should_segment_sentences_with_pinyin() {
+    use sentence_base::analyzer::jieba::JiebaAnalyzer;
+    use sentence_base::analyzer::part_of_speech::PartOfSpeech;
+    use sentence_base::analyzer::{analyze_sentence, Span};
+
+    let pinyin_dictionary = PinyinDictionary::parse(CEDICT.replace("% sample\n", "").as_bytes())
+        .expect("dictionary should parse");
+    let analyzer = JiebaAnalyzer::with_pinyin_dictionary(pinyin_dictionary);
+
+    let morphemes = analyze_sentence(&analyzer, "我是学生。");
+
+    assert_eq!(
+        morphemes
+            .iter()
+            .map(|morpheme| (
+                morpheme.morpheme.as_str(),
+                morpheme.dictionary_form.as_str(),
+                morpheme.reading.as_str(),
+                morpheme.part_of_speech,
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            ("我", "我", "wǒ", PartOfSpeech::Pronoun),
+            ("是", "是", "shì", PartOfSpeech::Verb),
+            ("学生", "学生", "xuésheng", PartOfSpeech::Noun),
+            ("。", "。", "。", PartOfSpeech::Symbol),
+        ]
+    );
+    assert_eq!(morphemes[2].offsets.scalar, Span { start: 2, end: 4 });
+    assert_eq!(morphemes[2].offsets.utf16, Span { start: 2, end: 4 });
+}
@@ -8,6 +8,7 @@ use rocket::serde::json::Value;
 use sentence_base;
 use sentence_base::jwt::{get_current_timestamp, get_jwt_secret_hmac, TokenClaims, TokenType};
 use sentence_base::models::user::User;
+use sentence_base::registration::RegistrationMode;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 static DATABASE_COUNT: AtomicUsize = AtomicUsize::new(0);
@@ -56,8 +57,14 @@ pub fn prepare_new_database() -> String {
 }
 
 pub fn create_client() -> (Client, String) {
+    create_client_with_registration_mode(RegistrationMode::Closed)
+}
+
+pub fn create_client_with_registration_mode(
+    registration_mode: RegistrationMode,
+) -> (Client, String) {
     let database_url = prepare_new_database();
-    let rocket = sentence_base::rocket(&database_url);
+    let rocket = sentence_base::rocket_with_registration_mode(&database_url, registration_mode);
 
     (
         Client::tracked(rocket).expect("client should launch"),
@@ -69,9 +76,23 @@ pub fn create_client_and_register_user(
     username: &str,
     email: &str,
     password: &str,
+) -> (Client, User, PgConnection) {
+    create_client_with_registration_mode_and_register_user(
+        RegistrationMode::Closed,
+        username,
+        email,
+        password,
+    )
+}
+
+pub fn create_client_with_registration_mode_and_register_user(
+    registration_mode: RegistrationMode,
+    username: &str,
+    email: &str,
+    password: &str,
 ) -> (Client, User, PgConnection) {
     let database_url = prepare_new_database();
-    let rocket = sentence_base::rocket(&database_url);
+    let rocket = sentence_base::rocket_with_registration_mode(&database_url, registration_mode);
     let database_connection = create_database_connection(&database_url);
     let user = User::register(
         &database_connection,
@@ -118,6 +139,35 @@ pub fn send_post_request_with_json_and_auth<'a>(
         .dispatch()
 }
 
+pub fn send_put_request_with_json_and_auth<'a>(
+    client: &'a Client,
+    url: &'a str,
+    token: &'a str,
+    json: Value,
+) -> LocalResponse<'a> {
+    client
+        .put(url)
+        .header(ContentType::JSON)
+        .header(Header::new("Authorization", format!("Bearer {}", &token)))
+        .body(json.to_string())
+        .dispatch()
+}
+
+pub fn send_put_request_with_body_and_auth<'a>(
+    client: &'a Client,
+    url: &'a str,
+    token: &'a str,
+    content_type: ContentType,
+    body: &'a str,
+) -> LocalResponse<'a> {
+    client
+        .put(url)
+        .header(content_type)
+        .header(Header::new("Authorization", format!("Bearer {}", &token)))
+        .body(body)
+        .dispatch()
+}
+
 pub fn send_get_request<'a>(client: &'a Client, url: &'a str) -> LocalResponse<'a> {
     client.get(url).dispatch()
 }
@@ -247,6 +297,8 @@ pub fn generate_jwt_token_for_user(user: &User, token_type: TokenType) -> String
         sub: user.id,
         gen: 0,
         typ: token_type,
+        sid: None,
+        jti: None,
     })
 }
 
@@ -0,0 +1,210 @@
+use common::*;
+use rocket::http::{ContentType, Status};
+use sentence_base::jwt::TokenType;
+use serde_json::json;
+
+mod common;
+
+#[test]
+fn upload_should_require_auth() {
+    let (client, _) = create_client();
+
+    let response = client
+        .put("/frequency-lists/custom")
+        .header(ContentType::JSON)
+        .body(r#"[["猫","ネコ"]]"#)
+        .dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    let json = response_to_json(response);
+    assert_fail(&json, "No Token Provided");
+}
+
+#[test]
+fn upload_should_store_json_frequency_list() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_put_request_with_json_and_auth(
+        &client,
+        "/frequency-lists/custom",
+        &access_token,
+        json!([["猫", "ネコ"], ["ペン", "ペン"], ["犬", "イヌ"]]),
+    );
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+    assert_eq!(json["data"]["word_count"], 3);
+
+    let response = send_get_request_with_auth(&client, "/frequency-lists/custom", &access_token);
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+    assert_eq!(json["data"]["word_count"], 3);
+
+    let response = send_post_request_with_json_and_auth(
+        &client,
+        "/frequency-lists/lookup",
+        &access_token,
+        json!({
+            "words": [
+                { "dictionary_form": "ペン", "reading": "ペン" },
+                { "dictionary_form": "家", "reading": "イエ" },
+            ]
+        }),
+    );
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_eq!(json["data"]["words"][0]["frequency"], 1);
+    assert_eq!(json["data"]["words"][0]["match_type"], "exact");
+    assert_eq!(json["data"]["words"][1]["frequency"], 4);
+    assert_eq!(json["data"]["words"][1]["match_type"], "not_found");
+}
+
+#[test]
+fn upload_should_store_csv_frequency_list() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_put_request_with_body_and_auth(
+        &client,
+        "/frequency-lists/custom",
+        &access_token,
+        ContentType::CSV,
+        "dictionary_form,reading,count\n猫,ネコ,3\nペン,ペン,7\n",
+    );
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+    assert_eq!(json["data"]["word_count"], 2);
+
+    // ペン, read as ぺん
+    let response = send_get_request_with_auth(
+        &client,
+        "/frequency-lists/lookup?dictionary_form=%E3%83%9A%E3%83%B3&reading=%E3%81%BA%E3%82%93",
+        &access_token,
+    );
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_eq!(json["data"]["frequency"], 0);
+    assert_eq!(json["data"]["match_type"], "normalized");
+}
+
+#[test]
+fn upload_should_replace_previous_frequency_list() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    for frequency_list in [
+        json!([["猫", "ネコ"], ["犬", "イヌ"]]),
+        json!([["犬", "イヌ"]]),
+    ] {
+        let response = send_put_request_with_json_and_auth(
+            &client,
+            "/frequency-lists/custom",
+            &access_token,
+            frequency_list,
+        );
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    let response = send_post_request_with_json_and_auth(
+        &client,
+        "/frequency-lists/lookup",
+        &access_token,
+        json!({
+            "words": [
+                { "dictionary_form": "犬", "reading": "イヌ" },
+                { "dictionary_form": "猫", "reading": "ネコ" },
+            ]
+        }),
+    );
+    let json = response_to_json(response);
+    assert_eq!(json["data"]["words"][0]["frequency"], 0);
+    assert_eq!(json["data"]["words"][1]["match_type"], "not_found");
+}
+
+#[test]
+fn upload_should_reject_invalid_frequency_lists() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_put_request_with_json_and_auth(
+        &client,
+        "/frequency-lists/custom",
+        &access_token,
+        json!({ "猫": "ネコ" }),
+    );
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    let json = response_to_json(response);
+    assert_fail(&json, "Invalid Frequency List");
+
+    let response = send_put_request_with_body_and_auth(
+        &client,
+        "/frequency-lists/custom",
+        &access_token,
+        ContentType::CSV,
+        "猫,ネコ,many\n犬,イヌ,lots\n",
+    );
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    let json = response_to_json(response);
+    assert_fail(&json, "Invalid Frequency List");
+
+    let response = send_put_request_with_json_and_auth(
+        &client,
+        "/frequency-lists/custom",
+        &access_token,
+        json!([]),
+    );
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    let json = response_to_json(response);
+    assert_fail(&json, "Frequency List Is Empty");
+}
+
+#[test]
+fn get_should_fail_without_custom_frequency_list() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_get_request_with_auth(&client, "/frequency-lists/custom", &access_token);
+    assert_eq!(response.status(), Status::NotFound);
+    let json = response_to_json(response);
+    assert_fail(&json, "Custom Frequency List Not Found");
+}
+
+#[test]
+fn delete_should_restore_shared_frequency_list() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_put_request_with_json_and_auth(
+        &client,
+        "/frequency-lists/custom",
+        &access_token,
+        json!([["ペン", "ペン"]]),
+    );
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = send_delete_request_with_auth(&client, "/frequency-lists/custom", &access_token);
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+
+    let response = send_delete_request_with_auth(&client, "/frequency-lists/custom", &access_token);
+    assert_eq!(response.status(), Status::NotFound);
+    let json = response_to_json(response);
+    assert_fail(&json, "Custom Frequency List Not Found");
+
+    let response = send_get_request_with_auth(
+        &client,
+        "/frequency-lists/lookup?dictionary_form=%E3%83%9A%E3%83%B3&reading=%E3%83%9A%E3%83%B3",
+        &access_token,
+    );
+    let json = response_to_json(response);
+    assert_eq!(json["data"]["frequency"], 2847);
+}
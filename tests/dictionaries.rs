@@ -0,0 +1,270 @@
+use sentence_base::analyzer::dictionary::{
+    FeatureMapping, IpadicFeatureMapping, NeologdFeatureMapping, UnidicFeatureMapping,
+};
+use sentence_base::analyzer::part_of_speech::PartOfSpeech;
+use sentence_base::analyzer::{analyze_sentence, Analyzer, Morpheme, Span, Token};
+use std::collections::HashMap;
+
+struct FixtureAnalyzer {
+    fixtures: HashMap<&'static str, Vec<(&'static str, &'static str)>>,
+    feature_mapping: Box<dyn FeatureMapping>,
+}
+
+impl FixtureAnalyzer {
+    fn new(
+        feature_mapping: Box<dyn FeatureMapping>,
+        fixtures: Vec<(&'static str, Vec<(&'static str, &'static str)>)>,
+    ) -> Self {
+        FixtureAnalyzer {
+            fixtures: fixtures.into_iter().collect(),
+            feature_mapping,
+        }
+    }
+}
+
+impl Analyzer for FixtureAnalyzer {
+    fn tokenize(&self, text: &str) -> Vec<Token> {
+        let mut byte_start = 0;
+
+        self.fixtures
+            .get(text)
+            .map(|tokens| {
+                tokens
+                    .iter()
+                    .map(|(surface, features)| {
+                        let token = Token {
+                            surface: surface.to_string(),
+                            byte_start,
+                            byte_end: byte_start + surface.len(),
+                            features: features.split(',').map(|f| f.to_string()).collect(),
+                        };
+                        byte_start = token.byte_end;
+                        token
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn feature_mapping(&self) -> &dyn FeatureMapping {
+        self.feature_mapping.as_ref()
+    }
+
+    fn configuration_key(&self) -> String {
+        "fixture".to_string()
+    }
+}
+
+fn summarize(morphemes: &[Morpheme]) -> Vec<(&str, &str, &str, PartOfSpeech)> {
+    morphemes
+        .iter()
+        .map(|morpheme| {
+            (
+                morpheme.morpheme.as_str(),
+                morpheme.dictionary_form.as_str(),
+                morpheme.reading.as_str(),
+                morpheme.part_of_speech,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn ipadic_should_map_features() {
+    let analyzer = FixtureAnalyzer::new(
+        Box::new(IpadicFeatureMapping),
+        vec![
+            (
+                "食べたぽぷぴ",
+                vec![
+                    ("食べ", "動詞,自立,*,*,一段,連用形,食べる,タベ,タベ"),
+                    ("た", "助動詞,*,*,*,特殊・タ,基本形,た,タ,タ"),
+                    ("ぽぷぴ", "名詞,一般,*,*,*,*,*"),
+                ],
+            ),
+            (
+                "食べる",
+                vec![("食べる", "動詞,自立,*,*,一段,基本形,食べる,タベル,タベル")],
+            ),
+            ("た", vec![("た", "助動詞,*,*,*,特殊・タ,基本形,た,タ,タ")]),
+        ],
+    );
+
+    let morphemes = analyze_sentence(&analyzer, "食べたぽぷぴ");
+
+    assert_eq!(
+        summarize(&morphemes),
+        vec![
+            ("食べ", "食べる", "タベル", PartOfSpeech::Verb),
+            ("た", "た", "タ", PartOfSpeech::AuxiliaryVerb),
+            ("ぽぷぴ", "ぽぷぴ", "ぽぷぴ", PartOfSpeech::Noun),
+        ]
+    );
+    assert_eq!(morphemes[0].part_of_speech_subcategories, vec!["自立"]);
+    assert_eq!(morphemes[0].conjugation_type, Some("一段".to_string()));
+    assert_eq!(morphemes[0].conjugation_form, Some("連用形".to_string()));
+    assert_eq!(morphemes[2].conjugation_type, None);
+    assert!(morphemes[2].furigana.is_none());
+}
+
+#[test]
+fn neologd_should_map_features() {
+    let analyzer = FixtureAnalyzer::new(
+        Box::new(NeologdFeatureMapping),
+        vec![
+            (
+                "きゃりーぱみゅぱみゅが歌う",
+                vec![
+                    (
+                        "きゃりーぱみゅぱみゅ",
+                        "名詞,固有名詞,人名,一般,*,*,きゃりーぱみゅぱみゅ,キャリーパミュパミュ,キャリーパミュパミュ",
+                    ),
+                    ("が", "助詞,格助詞,一般,*,*,*,が,ガ,ガ"),
+                    ("歌う", "動詞,自立,*,*,五段・ワ行促音便,基本形,歌う,ウタウ,ウタウ"),
+                ],
+            ),
+            (
+                "歌う",
+                vec![("歌う", "動詞,自立,*,*,五段・ワ行促音便,基本形,歌う,ウタウ,ウタウ")],
+            ),
+        ],
+    );
+
+    let morphemes = analyze_sentence(&analyzer, "きゃりーぱみゅぱみゅが歌う");
+
+    assert_eq!(
+        summarize(&morphemes),
+        vec![
+            (
+                "きゃりーぱみゅぱみゅ",
+                "きゃりーぱみゅぱみゅ",
+                "キャリーパミュパミュ",
+                PartOfSpeech::Noun
+            ),
+            ("が", "が", "ガ", PartOfSpeech::Particle),
+            ("歌う", "歌う", "ウタウ", PartOfSpeech::Verb),
+        ]
+    );
+    assert_eq!(
+        morphemes[0].part_of_speech_subcategories,
+        vec!["固有名詞", "人名", "一般"]
+    );
+}
+
+#[test]
+fn unidic_should_map_features() {
+    let analyzer = FixtureAnalyzer::new(
+        Box::new(UnidicFeatureMapping),
+        vec![(
+            "ペンを食べたぽぷぴ",
+            vec![
+                (
+                    "ペン",
+                    "名詞,普通名詞,一般,*,*,*,ペン,ペン-pen,ペン,ペン,ペン,ペン,外,*,*,*,*,*,*,体,ペン,ペン",
+                ),
+                (
+                    "を",
+                    "助詞,格助詞,*,*,*,*,ヲ,を,を,オ,を,オ,和,*,*,*,*,*,*,格助,ヲ,ヲ",
+                ),
+                (
+                    "食べ",
+                    "動詞,一般,*,*,下一段-バ行,連用形-一般,タベル,食べる,食べ,タベ,食べる,タベル,和,*,*,*,*,*,*,用,タベ,タベル",
+                ),
+                (
+                    "た",
+                    "助動詞,*,*,*,助動詞-タ,終止形-一般,タ,た,た,タ,た,タ,和,*,*,*,*,*,*,助動,タ,タ",
+                ),
+                ("ぽぷぴ", "名詞,普通名詞,一般,*,*,*"),
+            ],
+        )],
+    );
+
+    let morphemes = analyze_sentence(&analyzer, "ペンを食べたぽぷぴ");
+
+    assert_eq!(
+        summarize(&morphemes),
+        vec![
+            ("ペン", "ペン", "ペン", PartOfSpeech::Noun),
+            ("を", "を", "ヲ", PartOfSpeech::Particle),
+            ("食べ", "食べる", "タベル", PartOfSpeech::Verb),
+            ("た", "た", "タ", PartOfSpeech::AuxiliaryVerb),
+            ("ぽぷぴ", "ぽぷぴ", "ぽぷぴ", PartOfSpeech::Noun),
+        ]
+    );
+    assert_eq!(
+        morphemes[2].conjugation_type,
+        Some("下一段-バ行".to_string())
+    );
+    assert_eq!(
+        morphemes[2].conjugation_form,
+        Some("連用形-一般".to_string())
+    );
+    assert_eq!(
+        morphemes[2]
+            .furigana
+            .as_ref()
+            .and_then(|segments| segments.first())
+            .and_then(|segment| segment.reading.clone()),
+        Some("た".to_string())
+    );
+}
+
+#[test]
+fn analyze_sentence_should_count_offsets_across_tokens() {
+    let analyzer = FixtureAnalyzer::new(
+        Box::new(IpadicFeatureMapping),
+        vec![(
+            "𠮷野家で食べた",
+            vec![
+                ("𠮷野家", "名詞,固有名詞,組織,*,*,*,*"),
+                ("で", "助詞,格助詞,一般,*,*,*,で,デ,デ"),
+                ("食べ", "動詞,自立,*,*,一段,連用形,食べる,タベ,タベ"),
+                ("た", "助動詞,*,*,*,特殊・タ,基本形,た,タ,タ"),
+            ],
+        )],
+    );
+
+    let morphemes = analyze_sentence(&analyzer, "𠮷野家で食べた");
+
+    assert_eq!(
+        morphemes
+            .iter()
+            .map(|morpheme| (morpheme.offsets.utf16, morpheme.offsets.scalar))
+            .collect::<Vec<_>>(),
+        vec![
+            (Span { start: 0, end: 4 }, Span { start: 0, end: 3 }),
+            (Span { start: 4, end: 5 }, Span { start: 3, end: 4 }),
+            (Span { start: 5, end: 7 }, Span { start: 4, end: 6 }),
+            (Span { start: 7, end: 8 }, Span { start: 6, end: 7 }),
+        ]
+    );
+}
+
+#[cfg(feature = "lindera")]
+#[test]
+fn lindera_analyzer_should_tokenize_with_the_embedded_dictionary() {
+    use sentence_base::analyzer::lindera::LinderaAnalyzer;
+
+    let analyzer = LinderaAnalyzer::new();
+    let tokens = analyzer.tokenize("猫が食べた");
+
+    assert_eq!(
+        tokens
+            .iter()
+            .map(|token| (token.surface.as_str(), token.byte_start, token.byte_end))
+            .collect::<Vec<_>>(),
+        vec![("猫", 0, 3), ("が", 3, 6), ("食べ", 6, 12), ("た", 12, 15)]
+    );
+
+    let morphemes = analyze_sentence(&analyzer, "猫が食べた");
+
+    assert_eq!(
+        summarize(&morphemes),
+        vec![
+            ("猫", "猫", "ネコ", PartOfSpeech::Noun),
+            ("が", "が", "ガ", PartOfSpeech::Particle),
+            ("食べ", "食べる", "タベル", PartOfSpeech::Verb),
+            ("た", "た", "タ", PartOfSpeech::AuxiliaryVerb),
+        ]
+    );
+}
@@ -0,0 +1,55 @@
+use sentence_base::difficulty::{word_rarity, DifficultyWeights, DEFAULT_DIFFICULTY_WEIGHTS};
+use sentence_base::frequency_list::{FrequencyLookup, FrequencyMatch};
+
+#[test]
+fn difficulty_weights_should_parse_with_defaults_for_omitted_components() {
+    assert_eq!(
+        DifficultyWeights::parse(DEFAULT_DIFFICULTY_WEIGHTS),
+        Ok(DifficultyWeights::default())
+    );
+    assert_eq!(
+        DifficultyWeights::parse(" length : 0.1 , unknown_words:0"),
+        Ok(DifficultyWeights {
+            length: 0.1,
+            unknown_words: 0.0,
+            ..DifficultyWeights::default()
+        })
+    );
+
+    assert!(DifficultyWeights::parse("grammar:1").is_err());
+    assert!(DifficultyWeights::parse("length:-1").is_err());
+    assert!(DifficultyWeights::parse("length:NaN").is_err());
+    assert!(DifficultyWeights::parse("length").is_err());
+}
+
+#[test]
+fn word_rarity_should_grow_with_rank() {
+    let lookup = |frequency, match_type| FrequencyLookup {
+        frequency,
+        match_type,
+    };
+
+    assert_eq!(word_rarity(lookup(0, FrequencyMatch::Exact)), 0.0);
+    assert!(
+        word_rarity(lookup(100, FrequencyMatch::Exact))
+            < word_rarity(lookup(10000, FrequencyMatch::Normalized))
+    );
+    assert_eq!(word_rarity(lookup(1000000, FrequencyMatch::Exact)), 1.0);
+    assert_eq!(word_rarity(lookup(10, FrequencyMatch::NotFound)), 1.0);
+}
+
+#[test]
+fn difficulty_weights_should_score_sentences() {
+    let difficulty_weights = DifficultyWeights::default();
+
+    // 3 * 0.5 + 2 * 1 + 0.05 * 7 + 2 * 1 / 7
+    assert_eq!(
+        difficulty_weights.score("これは猫です。", &[0.25, 0.75], 1),
+        4.14
+    );
+    assert_eq!(difficulty_weights.score("", &[], 0), 0.0);
+    assert!(
+        difficulty_weights.score("猫です。", &[0.2], 0)
+            < difficulty_weights.score("魑魅魍魎です。", &[1.0], 1)
+    );
+}
@@ -0,0 +1,232 @@
+use common::*;
+use rocket::http::Status;
+use sentence_base::frequency_list::bands::{FrequencyBand, FrequencyBands};
+use sentence_base::frequency_list::{FrequencyLookup, FrequencyMatch};
+use sentence_base::jwt::TokenType;
+use serde_json::json;
+
+mod common;
+
+fn found(frequency: usize) -> FrequencyLookup {
+    FrequencyLookup {
+        frequency,
+        match_type: FrequencyMatch::Exact,
+    }
+}
+
+#[test]
+fn frequency_bands_should_parse_ranges() {
+    let frequency_bands = FrequencyBands::parse("common: 100, uncommon:2000 ,rare").unwrap();
+
+    assert_eq!(
+        frequency_bands.get_bands(),
+        &[
+            FrequencyBand {
+                name: "common".to_string(),
+                max_rank: Some(100),
+            },
+            FrequencyBand {
+                name: "uncommon".to_string(),
+                max_rank: Some(2000),
+            },
+            FrequencyBand {
+                name: "rare".to_string(),
+                max_rank: None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn frequency_bands_should_reject_invalid_ranges() {
+    for frequency_bands in [
+        "",
+        "common:100",
+        "rare,common:100",
+        "common:abc,rare",
+        "common:100,uncommon:100,rare",
+        ":100,rare",
+    ] {
+        assert!(
+            FrequencyBands::parse(frequency_bands).is_err(),
+            "{} should be rejected",
+            frequency_bands
+        );
+    }
+}
+
+#[test]
+fn frequency_bands_should_assign_bands_by_rank() {
+    let frequency_bands = FrequencyBands::default();
+
+    assert_eq!(frequency_bands.get_band(found(0)), "top_1k");
+    assert_eq!(frequency_bands.get_band(found(999)), "top_1k");
+    assert_eq!(frequency_bands.get_band(found(1000)), "1k_5k");
+    assert_eq!(frequency_bands.get_band(found(9999)), "5k_10k");
+    assert_eq!(frequency_bands.get_band(found(10000)), "rare");
+}
+
+#[test]
+fn frequency_bands_should_put_missing_words_in_last_band() {
+    let frequency_bands = FrequencyBands::default();
+
+    assert_eq!(
+        frequency_bands.get_band(FrequencyLookup {
+            frequency: 3,
+            match_type: FrequencyMatch::NotFound,
+        }),
+        "rare"
+    );
+}
+
+#[test]
+fn get_bands_should_list_configured_bands() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_get_request_with_auth(&client, "/frequency-lists/bands", &access_token);
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+
+    assert_eq!(
+        json.get("data").unwrap(),
+        &json!({
+            "bands": [
+                { "name": "top_1k", "max_rank": 1000 },
+                { "name": "1k_5k", "max_rank": 5000 },
+                { "name": "5k_10k", "max_rank": 10000 },
+                { "name": "rare", "max_rank": null },
+            ]
+        })
+    );
+}
+
+#[test]
+fn lookup_should_require_auth() {
+    let (client, _) = create_client();
+
+    let response = send_get_request(
+        &client,
+        "/frequency-lists/lookup?dictionary_form=%E3%83%9A%E3%83%B3",
+    );
+    assert_eq!(response.status(), Status::Unauthorized);
+    let json = response_to_json(response);
+    assert_fail(&json, "No Token Provided");
+}
+
+#[test]
+fn lookup_should_return_frequency_and_band() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    // ペン, read as ぺん
+    let response = send_get_request_with_auth(
+        &client,
+        "/frequency-lists/lookup?dictionary_form=%E3%83%9A%E3%83%B3&reading=%E3%81%BA%E3%82%93",
+        &access_token,
+    );
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+
+    assert_eq!(
+        json.get("data").unwrap(),
+        &json!({
+            "dictionary_form": "ペン",
+            "reading": "ぺん",
+            "frequency": 2847,
+            "match_type": "normalized",
+            "band": "1k_5k",
+        })
+    );
+}
+
+#[test]
+fn lookup_should_reject_empty_dictionary_form() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_get_request_with_auth(
+        &client,
+        "/frequency-lists/lookup?dictionary_form=%20",
+        &access_token,
+    );
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    let json = response_to_json(response);
+    assert_fail(&json, "Dictionary Form Is Required");
+}
+
+#[test]
+fn lookup_many_should_return_frequencies_in_order() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_post_request_with_json_and_auth(
+        &client,
+        "/frequency-lists/lookup",
+        &access_token,
+        json!({
+            "words": [
+                { "dictionary_form": "ぽぷぴ", "reading": "ポプピ" },
+                { "dictionary_form": "ペン", "reading": "ペン" },
+                { "dictionary_form": "ペン" },
+            ]
+        }),
+    );
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+
+    assert_eq!(
+        json.get("data").unwrap(),
+        &json!({
+            "words": [
+                {
+                    "dictionary_form": "ぽぷぴ",
+                    "reading": "ポプピ",
+                    "frequency": 102845,
+                    "match_type": "not_found",
+                    "band": "rare",
+                },
+                {
+                    "dictionary_form": "ペン",
+                    "reading": "ペン",
+                    "frequency": 2847,
+                    "match_type": "exact",
+                    "band": "1k_5k",
+                },
+                {
+                    "dictionary_form": "ペン",
+                    "reading": null,
+                    "frequency": 2847,
+                    "match_type": "dictionary_form",
+                    "band": "1k_5k",
+                },
+            ]
+        })
+    );
+}
+
+#[test]
+fn lookup_many_should_validate_words() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    for words in [json!([]), json!([{ "dictionary_form": " " }])] {
+        let response = send_post_request_with_json_and_auth(
+            &client,
+            "/frequency-lists/lookup",
+            &access_token,
+            json!({ "words": words }),
+        );
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+        let json = response_to_json(response);
+        assert_fail_reasons_validation_fields(&json, vec!["words".to_string()]);
+    }
+}
@@ -0,0 +1,441 @@
+use common::*;
+use rocket::http::Status;
+use sentence_base::frequency_list::counts::read_counted_entries;
+use sentence_base::frequency_list::yomitan::read_yomitan_frequency_dictionary;
+use sentence_base::frequency_list::{FrequencyList, FrequencyLists, JpFrequencyList};
+use sentence_base::jwt::TokenType;
+use sentence_base::language::Language;
+use sentence_base::models::user::User;
+use serde_json::json;
+use std::io::{Cursor, Write};
+use std::path::PathBuf;
+use zip::write::FileOptions;
+use zip::ZipWriter;
+
+mod common;
+
+fn create_frequency_list_directory(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!(
+        "sentence_base_frequency_lists_{}_{}",
+        name,
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&directory);
+    std::fs::create_dir_all(&directory).expect("directory should be created");
+
+    for (file_name, content) in files {
+        std::fs::write(directory.join(file_name), content).expect("file should be written");
+    }
+
+    directory
+}
+
+fn create_yomitan_archive(files: &[(&str, &str)]) -> Vec<u8> {
+    let mut zip_writer = ZipWriter::new(Cursor::new(vec![]));
+
+    for (file_name, content) in files {
+        zip_writer
+            .start_file(*file_name, FileOptions::default())
+            .expect("file should be started");
+        zip_writer
+            .write_all(content.as_bytes())
+            .expect("file should be written");
+    }
+
+    zip_writer
+        .finish()
+        .expect("archive should be finished")
+        .into_inner()
+}
+
+#[test]
+fn frequency_list_should_rank_words_by_position() {
+    let frequency_list = JpFrequencyList::from_json(r#"[["だ","ダ"],["猫","ネコ"],["猫","ネコ"]]"#)
+        .expect("frequency list should parse");
+
+    assert_eq!(frequency_list.get_frequency("だ", "ダ"), 0);
+    assert_eq!(frequency_list.get_frequency("猫", "ネコ"), 1);
+    assert_eq!(frequency_list.get_frequency("犬", "イヌ"), 3);
+}
+
+#[test]
+fn frequency_list_should_rank_duplicates_alike_in_json_and_csv() {
+    let json_frequency_list = JpFrequencyList::from_json(
+        r#"[["だ","ダ"],["猫","ネコ"],["犬","イヌ"],["猫","ネコ"],["本","ホン"]]"#,
+    )
+    .expect("frequency list should parse");
+    let csv_frequency_list = JpFrequencyList::from_ranked_entries(
+        read_counted_entries("だ,ダ,5\n猫,ネコ,4\n犬,イヌ,3\n猫,ネコ,2\n本,ホン,1\n".as_bytes())
+            .expect("frequency list should parse"),
+    );
+
+    assert_eq!(json_frequency_list.len(), csv_frequency_list.len());
+    for (word, reading, frequency) in [
+        ("だ", "ダ", 0),
+        ("猫", "ネコ", 1),
+        ("犬", "イヌ", 2),
+        ("本", "ホン", 3),
+    ] {
+        assert_eq!(json_frequency_list.get_frequency(word, reading), frequency);
+        assert_eq!(csv_frequency_list.get_frequency(word, reading), frequency);
+    }
+}
+
+#[test]
+fn frequency_list_should_reject_malformed_entries() {
+    assert!(JpFrequencyList::from_json(r#"[["だ"]]"#).is_err());
+    assert!(JpFrequencyList::from_json(r#"{"だ": "ダ"}"#).is_err());
+}
+
+#[test]
+fn bundled_frequency_list_should_match_json_frequency_list() {
+    let bundled_frequency_list = JpFrequencyList::bundled();
+    let json_frequency_list = JpFrequencyList::from_json(
+        &std::fs::read_to_string("frequency_lists/jp.json").expect("frequency list should exist"),
+    )
+    .expect("frequency list should parse");
+
+    assert_eq!(bundled_frequency_list.len(), json_frequency_list.len());
+    for (word, reading) in [
+        ("これ", "コレ"),
+        ("は", "ハ"),
+        ("ペン", "ペン"),
+        ("です", "デス"),
+        ("。", "。"),
+    ] {
+        assert_eq!(
+            bundled_frequency_list.get_frequency(word, reading),
+            json_frequency_list.get_frequency(word, reading)
+        );
+    }
+    assert_eq!(bundled_frequency_list.get_frequency("ペン", "ペン"), 2847);
+}
+
+#[test]
+fn frequency_lists_should_replace_bundled_list_from_directory() {
+    let directory =
+        create_frequency_list_directory("override", &[("jp.json", r#"[["猫","ネコ"]]"#)]);
+
+    let frequency_lists =
+        FrequencyLists::load(Some(&directory), "jp").expect("frequency lists should load");
+
+    assert_eq!(frequency_lists.get(Language::Japanese, None).len(), 1);
+    assert_eq!(
+        frequency_lists
+            .get(Language::Japanese, None)
+            .get_frequency("猫", "ネコ"),
+        0
+    );
+}
+
+#[test]
+fn frequency_lists_should_load_named_lists_from_directory() {
+    let directory = create_frequency_list_directory(
+        "named",
+        &[
+            ("anime.json", r#"[["猫","ネコ"],["だ","ダ"]]"#),
+            ("novels.json", r#"[["だ","ダ"]]"#),
+            ("notes.txt", "not a frequency list"),
+        ],
+    );
+
+    let frequency_lists =
+        FrequencyLists::load(Some(&directory), "novels").expect("frequency lists should load");
+
+    let infos = frequency_lists
+        .get_infos(Language::Japanese)
+        .into_iter()
+        .map(|info| (info.name, info.word_count, info.is_default))
+        .collect::<Vec<(String, usize, bool)>>();
+    assert_eq!(
+        infos,
+        vec![
+            ("anime".to_string(), 2, false),
+            ("jp".to_string(), 102844, false),
+            ("novels".to_string(), 1, true)
+        ]
+    );
+
+    assert_eq!(
+        frequency_lists
+            .get(Language::Japanese, Some("anime"))
+            .get_frequency("猫", "ネコ"),
+        0
+    );
+    assert_eq!(
+        frequency_lists
+            .get(Language::Japanese, None)
+            .get_frequency("猫", "ネコ"),
+        2
+    );
+    assert_eq!(
+        frequency_lists
+            .get(Language::Japanese, Some("missing"))
+            .get_frequency("猫", "ネコ"),
+        2
+    );
+}
+
+#[test]
+fn frequency_lists_should_require_default_list() {
+    let directory =
+        create_frequency_list_directory("missing_default", &[("anime.json", r#"[["猫","ネコ"]]"#)]);
+
+    assert!(FrequencyLists::load(Some(&directory), "novels").is_err());
+}
+
+#[test]
+fn yomitan_dictionary_should_map_entries_to_ranks() {
+    let archive = create_yomitan_archive(&[
+        (
+            "index.json",
+            r#"{"title": "Test", "format": 3, "revision": "1"}"#,
+        ),
+        (
+            "term_meta_bank_2.json",
+            r#"[
+                ["猫", "freq", {"reading": "ねこ", "frequency": {"value": 5, "displayValue": "5㋕"}}],
+                ["だ", "freq", 1],
+                ["猫", "pitch", {"reading": "ねこ", "pitches": [{"position": 1}]}]
+            ]"#,
+        ),
+        (
+            "term_meta_bank_1.json",
+            r#"[
+                ["ペン", "freq", {"value": 2, "displayValue": "2"}],
+                ["猫", "freq", {"reading": "びょう", "frequency": 30}],
+                ["犬", "freq", "12"]
+            ]"#,
+        ),
+    ]);
+
+    let frequency_list =
+        read_yomitan_frequency_dictionary(Cursor::new(archive)).expect("dictionary should be read");
+
+    assert_eq!(frequency_list.len(), 5);
+    assert_eq!(frequency_list.get_frequency("だ", "ダ"), 0);
+    assert_eq!(frequency_list.get_frequency("ペン", "ペン"), 1);
+    assert_eq!(frequency_list.get_frequency("猫", "ネコ"), 2);
+    assert_eq!(frequency_list.get_frequency("犬", "イヌ"), 3);
+    assert_eq!(frequency_list.get_frequency("猫", "ビョウ"), 4);
+    assert_eq!(frequency_list.get_frequency("鳥", "トリ"), 6);
+}
+
+#[test]
+fn yomitan_dictionary_should_rank_occurrence_based_values_descending() {
+    let archive = create_yomitan_archive(&[
+        (
+            "index.json",
+            r#"{"title": "Test", "format": 3, "revision": "1", "frequencyMode": "occurrence-based"}"#,
+        ),
+        (
+            "term_meta_bank_1.json",
+            r#"[["猫", "freq", 10], ["だ", "freq", 5000], ["犬", "freq", 200]]"#,
+        ),
+    ]);
+
+    let frequency_list =
+        read_yomitan_frequency_dictionary(Cursor::new(archive)).expect("dictionary should be read");
+
+    assert_eq!(frequency_list.get_frequency("だ", "ダ"), 0);
+    assert_eq!(frequency_list.get_frequency("犬", "イヌ"), 1);
+    assert_eq!(frequency_list.get_frequency("猫", "ネコ"), 2);
+}
+
+#[test]
+fn yomitan_dictionary_should_require_index() {
+    let archive = create_yomitan_archive(&[("term_meta_bank_1.json", r#"[["猫", "freq", 1]]"#)]);
+
+    assert!(read_yomitan_frequency_dictionary(Cursor::new(archive)).is_err());
+}
+
+#[test]
+fn frequency_lists_should_load_yomitan_archives() {
+    let directory = create_frequency_list_directory("yomitan", &[("jp.json", r#"[["だ","ダ"]]"#)]);
+    std::fs::write(
+        directory.join("community.zip"),
+        create_yomitan_archive(&[
+            ("index.json", r#"{"title": "Community", "revision": "1"}"#),
+            ("term_meta_bank_1.json", r#"[["猫", "freq", 1]]"#),
+        ]),
+    )
+    .expect("archive should be written");
+
+    let frequency_lists =
+        FrequencyLists::load(Some(&directory), "jp").expect("frequency lists should load");
+
+    assert!(frequency_lists.contains(Language::Japanese, "community"));
+    assert_eq!(
+        frequency_lists
+            .get(Language::Japanese, Some("community"))
+            .get_frequency("猫", "ネコ"),
+        0
+    );
+}
+
+#[test]
+fn get_all_should_require_auth() {
+    let (client, _) = create_client();
+
+    let response = send_get_request(&client, "/frequency-lists");
+    assert_eq!(response.status(), Status::Unauthorized);
+    let json = response_to_json(response);
+    assert_fail(&json, "No Token Provided");
+}
+
+#[test]
+fn get_all_should_list_frequency_lists() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_get_request_with_auth(&client, "/frequency-lists", &access_token);
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+
+    assert_eq!(
+        json.get("data").unwrap(),
+        &json!({
+            "frequency_lists": [
+                {
+                    "name": "jp",
+                    "word_count": 102844,
+                    "is_default": true,
+                }
+            ],
+            "selected": "jp",
+        })
+    );
+}
+
+#[test]
+fn select_should_reject_unknown_frequency_list() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_put_request_with_json_and_auth(
+        &client,
+        "/frequency-lists/selected",
+        &access_token,
+        json!({
+            "name": "missing"
+        }),
+    );
+    assert_eq!(response.status(), Status::NotFound);
+    let json = response_to_json(response);
+    assert_fail(&json, "Frequency List Not Found");
+}
+
+#[test]
+fn select_should_store_frequency_list_for_user() {
+    let (client, user, database_connection) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_put_request_with_json_and_auth(
+        &client,
+        "/frequency-lists/selected",
+        &access_token,
+        json!({
+            "name": "jp"
+        }),
+    );
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+
+    let user = User::find_by_id(&database_connection, user.id).expect("user should exist");
+    assert_eq!(user.frequency_list, Some("jp".to_string()));
+}
+
+#[test]
+fn counted_frequency_list_should_rank_words_by_count() {
+    let frequency_list = JpFrequencyList::from_ranked_entries(
+        read_counted_entries(
+            "dictionary_form,reading,count\n猫,ネコ,5\n犬,イヌ,12\n日,,5\n\"鳥\",トリ,1\n"
+                .as_bytes(),
+        )
+        .expect("frequency list should parse"),
+    );
+
+    assert_eq!(frequency_list.len(), 4);
+    assert_eq!(frequency_list.get_frequency("犬", "イヌ"), 0);
+    assert_eq!(frequency_list.get_frequency("猫", "ネコ"), 1);
+    assert_eq!(frequency_list.get_frequency("日", "ニチ"), 2);
+    assert_eq!(frequency_list.get_frequency("鳥", "トリ"), 3);
+    assert_eq!(frequency_list.get_frequency("馬", "ウマ"), 5);
+}
+
+#[test]
+fn counted_frequency_list_should_reject_malformed_rows() {
+    assert!(read_counted_entries("猫,ネコ,5\n犬,イヌ,many\n".as_bytes()).is_err());
+    assert!(read_counted_entries("猫\n".as_bytes()).is_err());
+    assert!(read_counted_entries(",ネコ,5\n".as_bytes()).is_err());
+}
+
+#[test]
+fn frequency_list_entries_should_rebuild_subsets() {
+    let frequency_list = JpFrequencyList::from_json(r#"[["だ","ダ"],["猫","ネコ"],["犬","イヌ"]]"#)
+        .expect("frequency list should parse");
+
+    let entries = frequency_list
+        .entries()
+        .into_iter()
+        .filter(|(dictionary_form, _, _)| dictionary_form == "犬")
+        .collect();
+    let subset = JpFrequencyList::from_entries(entries, frequency_list.len());
+
+    assert_eq!(subset.len(), 3);
+    assert_eq!(subset.get_frequency("犬", "イヌ"), 2);
+    assert_eq!(subset.get_frequency("猫", "ネコ"), 4);
+}
+
+#[test]
+fn frequency_lists_should_load_counted_lists_from_directory() {
+    let directory =
+        create_frequency_list_directory("counted", &[("novels.csv", "猫,ネコ,1\n犬,イヌ,2\n")]);
+
+    let frequency_lists =
+        FrequencyLists::load(Some(&directory), "novels").expect("frequency lists should load");
+
+    assert_eq!(
+        frequency_lists
+            .get(Language::Japanese, None)
+            .get_frequency("犬", "イヌ"),
+        0
+    );
+    assert_eq!(
+        frequency_lists
+            .get(Language::Japanese, None)
+            .get_frequency("猫", "ネコ"),
+        1
+    );
+}
+
+#[test]
+fn frequency_lists_should_load_chinese_lists_from_subdirectory() {
+    let directory =
+        create_frequency_list_directory("chinese", &[("novels.json", r#"[["猫","ネコ"]]"#)]);
+    std::fs::create_dir_all(directory.join("zh")).expect("directory should be created");
+    std::fs::write(
+        directory.join("zh").join("news.json"),
+        r#"["的", ["猫", "māo"]]"#,
+    )
+    .expect("file should be written");
+
+    let frequency_lists =
+        FrequencyLists::load(Some(&directory), "jp").expect("frequency lists should load");
+
+    assert!(!frequency_lists.contains(Language::Japanese, "news"));
+    assert!(frequency_lists.contains(Language::Chinese, "news"));
+    assert_eq!(frequency_lists.get_default_name(Language::Chinese), "zh");
+    assert!(frequency_lists.get(Language::Chinese, None).is_empty());
+    assert_eq!(
+        frequency_lists
+            .get(Language::Chinese, Some("news"))
+            .get_frequency("猫", "mao1"),
+        1
+    );
+}
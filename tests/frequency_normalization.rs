@@ -0,0 +1,91 @@
+use sentence_base::frequency_list::normalization::{normalize_reading, normalize_width};
+use sentence_base::frequency_list::{
+    FrequencyList, FrequencyLookup, FrequencyMatch, JpFrequencyList,
+};
+
+fn lookup(frequency: usize, match_type: FrequencyMatch) -> FrequencyLookup {
+    FrequencyLookup {
+        frequency,
+        match_type,
+    }
+}
+
+#[test]
+fn normalize_width_should_fold_width_variants() {
+    assert_eq!(normalize_width("ＰＣ　ｹﾞｰﾑ"), "PC ゲーム");
+    assert_eq!(normalize_width("ﾊﾟﾋﾞﾌﾞｳﾞｯ"), "パビブヴッ");
+    assert_eq!(normalize_width("猫"), "猫");
+}
+
+#[test]
+fn normalize_reading_should_unify_kana_and_long_vowels() {
+    assert_eq!(normalize_reading("らーめん"), "ラアメン");
+    assert_eq!(normalize_reading("ﾗｰﾒﾝ"), "ラアメン");
+    assert_eq!(normalize_reading("ラアメン"), "ラアメン");
+    assert_eq!(normalize_reading("コーヒー"), "コオヒイ");
+    assert_eq!(normalize_reading("ー"), "ー");
+}
+
+#[test]
+fn lookup_should_report_match_type() {
+    let frequency_list = JpFrequencyList::from_json(
+        r#"[["だ","ダ"],["ラーメン","ラーメン"],["ＰＣ","ピーシー"],["日","ヒ"],["日","ニチ"]]"#,
+    )
+    .expect("frequency list should parse");
+
+    assert_eq!(
+        frequency_list.lookup("ラーメン", "ラーメン"),
+        lookup(1, FrequencyMatch::Exact)
+    );
+    assert_eq!(
+        frequency_list.lookup("ラーメン", "らあめん"),
+        lookup(1, FrequencyMatch::Normalized)
+    );
+    assert_eq!(
+        frequency_list.lookup("PC", "ピーシー"),
+        lookup(2, FrequencyMatch::Normalized)
+    );
+    assert_eq!(
+        frequency_list.lookup("日", "ジツ"),
+        lookup(3, FrequencyMatch::DictionaryForm)
+    );
+    assert_eq!(
+        frequency_list.lookup("猫", "ネコ"),
+        lookup(6, FrequencyMatch::NotFound)
+    );
+}
+
+#[test]
+fn lookup_should_prefer_entries_without_reading_for_dictionary_form_matches() {
+    let frequency_list = JpFrequencyList::from_ranked_entries(vec![
+        ("日".to_string(), Some("ヒ".to_string())),
+        ("日".to_string(), None),
+    ]);
+
+    assert_eq!(
+        frequency_list.lookup("日", "ニチ"),
+        lookup(1, FrequencyMatch::DictionaryForm)
+    );
+}
+
+#[test]
+fn bundled_lookup_should_normalize_readings() {
+    let frequency_list = JpFrequencyList::bundled();
+
+    assert_eq!(
+        frequency_list.lookup("ペン", "ペン"),
+        lookup(2847, FrequencyMatch::Exact)
+    );
+    assert_eq!(
+        frequency_list.lookup("ペン", "ぺん"),
+        lookup(2847, FrequencyMatch::Normalized)
+    );
+    assert_eq!(
+        frequency_list.lookup("ペン", "ホゲ"),
+        lookup(2847, FrequencyMatch::DictionaryForm)
+    );
+    assert_eq!(
+        frequency_list.lookup("ぽぷぴ", "ポプピ"),
+        lookup(102845, FrequencyMatch::NotFound)
+    );
+}
@@ -0,0 +1,209 @@
+use chrono::{Duration, Utc};
+use common::*;
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+use sentence_base::jwt::TokenType;
+use sentence_base::models::invite::Invite;
+use sentence_base::models::user::User;
+use sentence_base::registration::RegistrationMode;
+use serde_json::{json, Value};
+
+mod common;
+
+fn create_invite_only_client_and_register_user() -> (Client, User, diesel::PgConnection) {
+    create_client_with_registration_mode_and_register_user(
+        RegistrationMode::InviteOnly,
+        TEST_USERNAME,
+        TEST_EMAIL,
+        TEST_PASSWORD,
+    )
+}
+
+fn create_invite(client: &Client, access_token: &String, body: Value) -> Value {
+    let response = send_post_request_with_json_and_auth(client, "/invites", access_token, body);
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+
+    json["data"].clone()
+}
+
+fn register_with_invite_code(client: &Client, username: &str, invite_code: Value) -> Value {
+    let response = send_post_request_with_json(
+        client,
+        "/auth/register",
+        json!({
+            "username": username,
+            "email": format!("{}@domain.com", username),
+            "password": TEST_PASSWORD,
+            "invite_code": invite_code,
+        }),
+    );
+
+    response_to_json(response)
+}
+
+#[test]
+fn registration_mode_should_parse() {
+    assert_eq!("open".parse(), Ok(RegistrationMode::Open));
+    assert_eq!("invite_only".parse(), Ok(RegistrationMode::InviteOnly));
+    assert_eq!(" closed ".parse(), Ok(RegistrationMode::Closed));
+    assert!("invite".parse::<RegistrationMode>().is_err());
+}
+
+#[test]
+fn new_should_require_auth() {
+    let (client, _) = create_client();
+    let response = send_post_request_with_json(&client, "/invites", json!({}));
+
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[test]
+fn new_should_validate() {
+    let (client, user, _) = create_invite_only_client_and_register_user();
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_post_request_with_json_and_auth(
+        &client,
+        "/invites",
+        &access_token,
+        json!({ "uses": 0, "expires_in": 0 }),
+    );
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    let json = response_to_json(response);
+    assert_fail(&json, "Validation Error");
+    assert_fail_reasons_validation_fields(
+        &json,
+        vec!["uses".to_string(), "expires_in".to_string()],
+    );
+}
+
+#[test]
+fn new_should_reject_out_of_range_expiry() {
+    let (client, user, _) = create_invite_only_client_and_register_user();
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_post_request_with_json_and_auth(
+        &client,
+        "/invites",
+        &access_token,
+        json!({ "expires_in": i64::MAX }),
+    );
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    let json = response_to_json(response);
+    assert_fail(&json, "Validation Error");
+    assert_fail_reasons_validation_fields(&json, vec!["expires_in".to_string()]);
+}
+
+#[test]
+fn new_should_create_a_single_use_invite_by_default() {
+    let (client, user, _) = create_invite_only_client_and_register_user();
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let invite = create_invite(&client, &access_token, json!({}));
+
+    assert_eq!(invite["created_by"], user.id);
+    assert_eq!(invite["remaining_uses"], 1);
+    assert_eq!(invite["expires_at"], Value::Null);
+    assert_eq!(invite["code"].as_str().unwrap().len(), 16);
+
+    let response = send_get_request_with_auth(&client, "/invites", &access_token);
+    let json = response_to_json(response);
+    assert_eq!(json["data"]["invites"], json!([invite]));
+}
+
+#[test]
+fn register_should_require_a_valid_invite_code() {
+    let (client, user, _) = create_invite_only_client_and_register_user();
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+    create_invite(&client, &access_token, json!({}));
+
+    let json = register_with_invite_code(&client, "first", Value::Null);
+    assert_fail(&json, "Invalid Invite Code");
+
+    let json = register_with_invite_code(&client, "first", json!("not-an-invite"));
+    assert_fail(&json, "Invalid Invite Code");
+}
+
+#[test]
+fn register_should_use_up_invites() {
+    let (client, user, _) = create_invite_only_client_and_register_user();
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+    let invite = create_invite(&client, &access_token, json!({ "uses": 2 }));
+
+    for username in ["first", "second"] {
+        let json = register_with_invite_code(&client, username, invite["code"].clone());
+        assert_success(&json);
+        assert_eq!(json["data"]["username"], username);
+    }
+
+    let json = register_with_invite_code(&client, "third", invite["code"].clone());
+    assert_fail(&json, "Invalid Invite Code");
+}
+
+#[test]
+fn register_should_not_use_up_invites_on_failure() {
+    let (client, user, _) = create_invite_only_client_and_register_user();
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+    let invite = create_invite(&client, &access_token, json!({}));
+
+    let json = register_with_invite_code(&client, TEST_USERNAME, invite["code"].clone());
+    assert_fail(&json, "Validation Error");
+
+    let json = register_with_invite_code(&client, "first", invite["code"].clone());
+    assert_success(&json);
+}
+
+#[test]
+fn register_should_reject_expired_invites() {
+    let (client, user, database_connection) = create_invite_only_client_and_register_user();
+    let invite = Invite::new(
+        &database_connection,
+        &user,
+        1,
+        Some(Utc::now().naive_utc() - Duration::seconds(1)),
+    )
+    .expect("invite should be created");
+
+    let json = register_with_invite_code(&client, "first", json!(invite.code));
+    assert_fail(&json, "Invalid Invite Code");
+}
+
+#[test]
+fn delete_should_revoke_invites() {
+    let (client, user, _) = create_invite_only_client_and_register_user();
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+    let invite = create_invite(&client, &access_token, json!({}));
+
+    let url = format!("/invites/{}", invite["id"]);
+    let response = send_delete_request_with_auth(&client, &url, &access_token);
+    assert_eq!(response.status(), Status::Ok);
+
+    let json = register_with_invite_code(&client, "first", invite["code"].clone());
+    assert_fail(&json, "Invalid Invite Code");
+
+    let response = send_delete_request_with_auth(&client, &url, &access_token);
+    assert_eq!(response.status(), Status::NotFound);
+    let json = response_to_json(response);
+    assert_fail(&json, "Invite Not Found");
+}
+
+#[test]
+fn delete_should_not_revoke_non_owned_invites() {
+    let (client, user, database_connection) = create_invite_only_client_and_register_user();
+    let other_user = User::register(
+        &database_connection,
+        "other".to_string(),
+        "other@domain.com".to_string(),
+        TEST_PASSWORD.to_string(),
+    )
+    .expect("user should be registered");
+    let invite =
+        Invite::new(&database_connection, &other_user, 1, None).expect("invite should be created");
+
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+    let url = format!("/invites/{}", invite.id);
+    let response = send_delete_request_with_auth(&client, &url, &access_token);
+    assert_eq!(response.status(), Status::NotFound);
+}
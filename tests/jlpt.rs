@@ -0,0 +1,90 @@
+use sentence_base::frequency_list::jlpt::{JlptLevel, JlptList};
+use sentence_base::language::Language;
+use serde_json::json;
+
+#[test]
+fn jlpt_level_should_parse_names_and_numbers() {
+    assert_eq!("N5".parse::<JlptLevel>(), Ok(JlptLevel::N5));
+    assert_eq!("n3".parse::<JlptLevel>(), Ok(JlptLevel::N3));
+    assert_eq!("1".parse::<JlptLevel>(), Ok(JlptLevel::N1));
+    assert!("N6".parse::<JlptLevel>().is_err());
+    assert!("N".parse::<JlptLevel>().is_err());
+
+    assert_eq!(json!(JlptLevel::N2), json!("N2"));
+    assert!(JlptLevel::N5 < JlptLevel::N1);
+}
+
+#[test]
+fn bundled_jlpt_list_should_cover_every_level() {
+    let jlpt_list = JlptList::bundled();
+
+    assert!(!jlpt_list.is_empty());
+    assert_eq!(
+        jlpt_list.lookup(Language::Japanese, "猫", Some("ネコ")),
+        Some(JlptLevel::N5)
+    );
+    assert_eq!(
+        jlpt_list.lookup(Language::Japanese, "経験", Some("ケイケン")),
+        Some(JlptLevel::N4)
+    );
+    assert_eq!(
+        jlpt_list.lookup(Language::Japanese, "影響", Some("エイキョウ")),
+        Some(JlptLevel::N3)
+    );
+    assert_eq!(
+        jlpt_list.lookup(Language::Japanese, "把握", Some("ハアク")),
+        Some(JlptLevel::N2)
+    );
+    assert_eq!(
+        jlpt_list.lookup(Language::Japanese, "矛盾", Some("ムジュン")),
+        Some(JlptLevel::N1)
+    );
+    assert_eq!(
+        jlpt_list.lookup(Language::Japanese, "魑魅魍魎", Some("チミモウリョウ")),
+        None
+    );
+}
+
+#[test]
+fn jlpt_list_should_match_normalized_readings_and_dictionary_forms() {
+    let jlpt_list = JlptList::from_json(
+        r#"[["生", "ナマ", 3], ["生", "セイ", "N2"], ["ペン", "ペン", 5], ["今日", "キョウ", 5]]"#,
+    )
+    .expect("JLPT list should parse");
+
+    assert_eq!(jlpt_list.len(), 4);
+    assert_eq!(
+        jlpt_list.lookup(Language::Japanese, "生", Some("せい")),
+        Some(JlptLevel::N2)
+    );
+    assert_eq!(
+        jlpt_list.lookup(Language::Japanese, "ﾍﾟﾝ", Some("ペン")),
+        Some(JlptLevel::N5)
+    );
+    assert_eq!(
+        jlpt_list.lookup(Language::Japanese, "生", None),
+        Some(JlptLevel::N3)
+    );
+    assert_eq!(
+        jlpt_list.lookup(Language::Japanese, "今日", Some("コンニチ")),
+        Some(JlptLevel::N5)
+    );
+}
+
+#[test]
+fn jlpt_list_should_only_tag_japanese_words() {
+    let jlpt_list = JlptList::from_json(r#"[["学生", "ガクセイ", 5]]"#).unwrap();
+
+    assert_eq!(
+        jlpt_list.lookup(Language::Chinese, "学生", Some("xuésheng")),
+        None
+    );
+}
+
+#[test]
+fn jlpt_list_should_reject_malformed_entries() {
+    assert!(JlptList::from_json(r#"[["猫", "ネコ"]]"#).is_err());
+    assert!(JlptList::from_json(r#"[["猫", "ネコ", 6]]"#).is_err());
+    assert!(JlptList::from_json(r#"[["猫", "ネコ", "N0"]]"#).is_err());
+    assert!(JlptList::from_json(r#"{"猫": 5}"#).is_err());
+}
@@ -0,0 +1,202 @@
+use common::*;
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+use rocket::serde::json::Value;
+use sentence_base::frequency_list::jlpt::JlptLevel;
+use sentence_base::jwt::TokenType;
+use sentence_base::kanji::{extract_kanji, KanjiInfo, KanjiList};
+use serde_json::json;
+
+mod common;
+
+#[test]
+fn extract_kanji_should_skip_kana_and_iteration_marks() {
+    assert_eq!(
+        extract_kanji("時々学校へ行く"),
+        vec!["時", "学", "校", "行"]
+    );
+    assert_eq!(extract_kanji("学生の学校"), vec!["学", "生", "校"]);
+    assert!(extract_kanji("これはペンです。").is_empty());
+}
+
+#[test]
+fn bundled_kanji_list_should_include_grades_and_jlpt_levels() {
+    let kanji_list = KanjiList::bundled();
+
+    assert!(!kanji_list.is_empty());
+    assert_eq!(
+        kanji_list.get("日"),
+        KanjiInfo {
+            grade: Some(1),
+            jlpt_level: Some(JlptLevel::N5),
+        }
+    );
+    assert_eq!(
+        kanji_list.get("妹"),
+        KanjiInfo {
+            grade: Some(2),
+            jlpt_level: Some(JlptLevel::N4),
+        }
+    );
+    assert_eq!(kanji_list.get("魑"), KanjiInfo::default());
+}
+
+#[test]
+fn kanji_list_should_reject_malformed_entries() {
+    assert!(KanjiList::from_json(r#"[["日", 1, 5], ["語", 8, null], ["魑", null, "N1"]]"#).is_ok());
+    assert!(KanjiList::from_json(r#"[["日", 7, 5]]"#).is_err());
+    assert!(KanjiList::from_json(r#"[["日本", 1, 5]]"#).is_err());
+    assert!(KanjiList::from_json(r#"[["日", 1]]"#).is_err());
+}
+
+fn add_sentence(
+    client: &Client,
+    access_token: &str,
+    dictionary_form: &str,
+    reading: &str,
+) -> Value {
+    let response = send_post_request_with_json_and_auth(
+        client,
+        "/sentences",
+        access_token,
+        json!({
+            "dictionary_form": dictionary_form,
+            "reading": reading,
+            "sentence": format!("{}です。", dictionary_form),
+        }),
+    );
+    assert_eq!(response.status(), Status::Ok);
+
+    response_to_json(response)["data"]["sentence"].clone()
+}
+
+#[test]
+fn get_all_should_require_auth() {
+    let (client, _) = create_client();
+
+    let response = send_get_request(&client, "/kanji");
+    assert_eq!(response.status(), Status::Unauthorized);
+    let json = response_to_json(response);
+    assert_fail(&json, "No Token Provided");
+}
+
+#[test]
+fn get_all_should_count_words_and_mined_words() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let sentence_id =
+        add_sentence(&client, &access_token, "学校", "ガッコウ")["sentence_id"].clone();
+    add_sentence(&client, &access_token, "学生", "ガクセイ");
+
+    let response = send_post_request_with_json_and_auth(
+        &client,
+        "/sentences/batches",
+        &access_token,
+        json!({ "sentences": [sentence_id] }),
+    );
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = send_get_request_with_auth(&client, "/kanji", &access_token);
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+
+    let kanji = json["data"]["kanji"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|kanji| {
+            (
+                kanji["kanji"].as_str().unwrap().to_string(),
+                kanji["word_count"].as_i64().unwrap(),
+                kanji["mined_word_count"].as_i64().unwrap(),
+                kanji["grade"].as_i64(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        kanji,
+        vec![
+            ("学".to_string(), 2, 1, Some(1)),
+            ("校".to_string(), 1, 1, Some(1)),
+            ("生".to_string(), 1, 0, Some(1)),
+        ]
+    );
+    assert_eq!(json["data"]["kanji"][0]["jlpt_level"], "N5");
+    assert!(json["data"]["kanji"][0]["first_seen_at"].is_string());
+}
+
+#[test]
+fn get_should_return_the_words_of_a_kanji() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    add_sentence(&client, &access_token, "学校", "ガッコウ");
+    add_sentence(&client, &access_token, "学生", "ガクセイ");
+
+    // 学
+    let response = send_get_request_with_auth(&client, "/kanji/%E5%AD%A6", &access_token);
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+
+    assert_eq!(json["data"]["kanji"], "学");
+    assert_eq!(json["data"]["word_count"], 2);
+    assert_eq!(json["data"]["mined_word_count"], 0);
+    assert_eq!(
+        json["data"]["words"],
+        json!([
+            { "dictionary_form": "学校", "reading": "ガッコウ", "is_mined": false },
+            { "dictionary_form": "学生", "reading": "ガクセイ", "is_mined": false },
+        ])
+    );
+}
+
+#[test]
+fn get_should_fail_for_unseen_or_invalid_kanji() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    // 猫
+    let response = send_get_request_with_auth(&client, "/kanji/%E7%8C%AB", &access_token);
+    assert_eq!(response.status(), Status::NotFound);
+    let json = response_to_json(response);
+    assert_fail(&json, "Kanji Not Found");
+
+    let response = send_get_request_with_auth(&client, "/kanji/a", &access_token);
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    let json = response_to_json(response);
+    assert_fail(&json, "Invalid Kanji");
+}
+
+#[test]
+fn pending_sentences_should_flag_new_kanji() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let sentence = add_sentence(&client, &access_token, "学校", "ガッコウ");
+    assert_eq!(sentence["new_kanji"], json!(["学", "校"]));
+    assert_eq!(sentence["has_new_kanji"], true);
+
+    let response = send_post_request_with_json_and_auth(
+        &client,
+        "/sentences/batches",
+        &access_token,
+        json!({ "sentences": [sentence["sentence_id"]] }),
+    );
+    assert_eq!(response.status(), Status::Ok);
+
+    let sentence = add_sentence(&client, &access_token, "学生", "ガクセイ");
+    assert_eq!(sentence["new_kanji"], json!(["生"]));
+    assert_eq!(sentence["has_new_kanji"], true);
+
+    let sentence = add_sentence(&client, &access_token, "ペン", "ペン");
+    assert_eq!(sentence["new_kanji"], json!([]));
+    assert_eq!(sentence["has_new_kanji"], false);
+}
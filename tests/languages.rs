@@ -0,0 +1,169 @@
+use common::*;
+use rocket::http::Status;
+use sentence_base::jwt::TokenType;
+use sentence_base::language::Language;
+use sentence_base::models::user::User;
+use serde_json::json;
+
+mod common;
+
+#[test]
+fn language_should_round_trip_codes() {
+    for language in Language::ALL {
+        assert_eq!(Language::from_code(language.code()), Some(language));
+    }
+
+    assert_eq!(Language::from_code("ko"), None);
+    assert_eq!(Language::default(), Language::Japanese);
+    assert_eq!(json!(Language::Chinese), json!("zh"));
+}
+
+#[test]
+fn get_all_should_require_auth() {
+    let (client, _) = create_client();
+
+    let response = send_get_request(&client, "/languages");
+    assert_eq!(response.status(), Status::Unauthorized);
+    let json = response_to_json(response);
+    assert_fail(&json, "No Token Provided");
+}
+
+#[test]
+fn get_all_should_default_to_japanese() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_get_request_with_auth(&client, "/languages", &access_token);
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+    assert_eq!(json["data"]["selected"], "ja");
+    assert!(json["data"]["languages"]
+        .as_array()
+        .unwrap()
+        .contains(&json!("ja")));
+}
+
+#[test]
+fn select_should_reject_unknown_language() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_put_request_with_json_and_auth(
+        &client,
+        "/languages/selected",
+        &access_token,
+        json!({
+            "language": "ko"
+        }),
+    );
+    assert_eq!(response.status(), Status::NotFound);
+    let json = response_to_json(response);
+    assert_fail(&json, "Language Not Found");
+}
+
+#[cfg(feature = "jieba")]
+fn select_chinese(client: &rocket::local::blocking::Client, access_token: &str) {
+    let response = send_put_request_with_json_and_auth(
+        client,
+        "/languages/selected",
+        access_token,
+        json!({
+            "language": "zh"
+        }),
+    );
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+    assert_eq!(json["data"]["selected"], "zh");
+}
+
+#[cfg(feature = "jieba")]
+#[test]
+fn select_should_store_language_for_user() {
+    let (client, user, database_connection) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    select_chinese(&client, &access_token);
+
+    let user = User::find_by_id(&database_connection, user.id).expect("user should exist");
+    assert_eq!(user.language, Language::Chinese);
+
+    let response = send_get_request_with_auth(&client, "/frequency-lists", &access_token);
+    let json = response_to_json(response);
+    assert_eq!(json["data"]["selected"], "zh");
+}
+
+#[cfg(feature = "jieba")]
+#[test]
+fn analyze_should_use_the_analyzer_of_the_selected_language() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    select_chinese(&client, &access_token);
+
+    let response = send_post_request_with_json_and_auth(
+        &client,
+        "/analyze",
+        &access_token,
+        json!({
+            "sentence": "我是学生。"
+        }),
+    );
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+
+    let morphemes = json["data"]["morphemes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|morpheme| morpheme["morpheme"].as_str().unwrap().to_string())
+        .collect::<Vec<String>>();
+    assert_eq!(morphemes, vec!["我", "是", "学生", "。"]);
+}
+
+#[cfg(feature = "jieba")]
+#[test]
+fn pending_sentences_should_be_kept_per_language() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_post_request_with_json_and_auth(
+        &client,
+        "/sentences",
+        &access_token,
+        json!({
+            "dictionary_form": "ペン",
+            "reading": "ペン",
+            "sentence": "これはペンです。",
+        }),
+    );
+    assert_eq!(response.status(), Status::Ok);
+
+    select_chinese(&client, &access_token);
+
+    let response = send_post_request_with_json_and_auth(
+        &client,
+        "/sentences",
+        &access_token,
+        json!({
+            "dictionary_form": "学生",
+            "reading": "xuésheng",
+            "sentence": "我是学生。",
+        }),
+    );
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = send_get_request_with_auth(&client, "/sentences", &access_token);
+    let json = response_to_json(response);
+    let sentences = json["data"]["sentences"].as_array().unwrap();
+    assert_eq!(sentences.len(), 1);
+    assert_eq!(sentences[0]["dictionary_form"], "学生");
+    assert_eq!(sentences[0]["dictionary_frequency_match"], "not_found");
+}
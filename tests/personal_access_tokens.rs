@@ -0,0 +1,300 @@
+use chrono::{Duration, Utc};
+use common::*;
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+use sentence_base::jwt::TokenType;
+use sentence_base::models::personal_access_token::{
+    PersonalAccessToken, PERSONAL_ACCESS_TOKEN_PREFIX,
+};
+use sentence_base::models::user::User;
+use sentence_base::scope::Scope;
+use serde_json::{json, Value};
+
+mod common;
+
+fn create_personal_access_token(client: &Client, access_token: &String, body: Value) -> Value {
+    let response = send_post_request_with_json_and_auth(client, "/auth/tokens", access_token, body);
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+
+    json["data"].clone()
+}
+
+#[test]
+fn scope_should_parse_codes() {
+    for scope in Scope::ALL {
+        assert_eq!(Scope::from_code(scope.code()), Some(scope));
+    }
+
+    assert_eq!(
+        Scope::from_code("sentences:write"),
+        Some(Scope::SentencesWrite)
+    );
+    assert_eq!(Scope::from_code("sentences"), None);
+}
+
+#[test]
+fn new_should_require_auth() {
+    let (client, _) = create_client();
+    let response = send_post_request_with_json(&client, "/auth/tokens", json!({}));
+
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[test]
+fn new_should_validate() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_post_request_with_json_and_auth(
+        &client,
+        "/auth/tokens",
+        &access_token,
+        json!({ "name": "", "scopes": [], "expires_in": 0 }),
+    );
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    let json = response_to_json(response);
+    assert_fail(&json, "Validation Error");
+    assert_fail_reasons_validation_fields(
+        &json,
+        vec![
+            "name".to_string(),
+            "scopes".to_string(),
+            "expires_in".to_string(),
+        ],
+    );
+}
+
+#[test]
+fn new_should_reject_out_of_range_expiry() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_post_request_with_json_and_auth(
+        &client,
+        "/auth/tokens",
+        &access_token,
+        json!({ "name": "Import script", "scopes": ["analyze"], "expires_in": i64::MAX }),
+    );
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    let json = response_to_json(response);
+    assert_fail(&json, "Validation Error");
+    assert_fail_reasons_validation_fields(&json, vec!["expires_in".to_string()]);
+}
+
+#[test]
+fn new_should_reject_unknown_scopes() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_post_request_with_json_and_auth(
+        &client,
+        "/auth/tokens",
+        &access_token,
+        json!({ "name": "Import script", "scopes": ["analyze", "everything"] }),
+    );
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    assert_fail(&response_to_json(response), "Invalid Scope");
+}
+
+#[test]
+fn new_should_show_the_token_only_once() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let personal_access_token = create_personal_access_token(
+        &client,
+        &access_token,
+        json!({
+            "name": "Mining extension",
+            "scopes": ["sentences:write", "analyze", "sentences:write"],
+        }),
+    );
+
+    assert_eq!(personal_access_token["name"], "Mining extension");
+    assert_eq!(
+        personal_access_token["scopes"],
+        json!(["analyze", "sentences:write"])
+    );
+    assert_eq!(personal_access_token["expires_at"], Value::Null);
+    assert_eq!(personal_access_token["last_used_at"], Value::Null);
+    assert!(personal_access_token["token"]
+        .as_str()
+        .unwrap()
+        .starts_with(PERSONAL_ACCESS_TOKEN_PREFIX));
+
+    let response = send_get_request_with_auth(&client, "/auth/tokens", &access_token);
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    let tokens = json["data"]["tokens"].as_array().unwrap();
+    assert_eq!(tokens.len(), 1);
+    assert_eq!(tokens[0]["id"], personal_access_token["id"]);
+    assert_eq!(tokens[0].get("token"), None);
+    assert_eq!(tokens[0].get("token_hash"), None);
+}
+
+#[test]
+fn token_should_only_grant_its_scopes() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+    let personal_access_token = create_personal_access_token(
+        &client,
+        &access_token,
+        json!({ "name": "Import script", "scopes": ["sentences:read"] }),
+    );
+    let token = personal_access_token["token"].as_str().unwrap().to_string();
+
+    let response = send_get_request_with_auth(&client, "/sentences", &token);
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = send_get_request_with_auth(&client, "/sentences/batches", &token);
+    assert_eq!(response.status(), Status::Forbidden);
+    assert_fail(
+        &response_to_json(response),
+        "Token with Insufficient Scope Provided",
+    );
+
+    let response = send_get_request_with_auth(&client, "/auth/tokens", &token);
+    assert_eq!(response.status(), Status::Forbidden);
+
+    let response = send_get_request_with_auth(&client, "/auth/tokens", &access_token);
+    let json = response_to_json(response);
+    assert_ne!(json["data"]["tokens"][0]["last_used_at"], Value::Null);
+}
+
+#[test]
+fn token_should_expire() {
+    let (client, user, database_connection) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let (_, token) = PersonalAccessToken::new(
+        &database_connection,
+        &user,
+        "Import script".to_string(),
+        vec![Scope::SentencesRead],
+        Some(Utc::now().naive_utc() - Duration::seconds(1)),
+    )
+    .expect("personal access token should be created");
+
+    let response = send_get_request_with_auth(&client, "/sentences", &token);
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_fail(&response_to_json(response), "Expired Token Provided");
+}
+
+#[test]
+fn unknown_token_should_be_rejected() {
+    let (client, _) = create_client();
+    let token = format!("{}{}", PERSONAL_ACCESS_TOKEN_PREFIX, "a".repeat(40));
+
+    let response = send_get_request_with_auth(&client, "/sentences", &token);
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_fail(&response_to_json(response), "Revoked Token Provided");
+}
+
+#[test]
+fn delete_should_revoke_tokens() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+    let personal_access_token = create_personal_access_token(
+        &client,
+        &access_token,
+        json!({ "name": "Import script", "scopes": ["sentences:read"] }),
+    );
+    let token = personal_access_token["token"].as_str().unwrap().to_string();
+
+    let url = format!("/auth/tokens/{}", personal_access_token["id"]);
+    let response = send_delete_request_with_auth(&client, &url, &access_token);
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = send_get_request_with_auth(&client, "/sentences", &token);
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_fail(&response_to_json(response), "Revoked Token Provided");
+
+    let response = send_delete_request_with_auth(&client, &url, &access_token);
+    assert_eq!(response.status(), Status::NotFound);
+    assert_fail(&response_to_json(response), "Token Not Found");
+}
+
+#[test]
+fn delete_should_not_revoke_non_owned_tokens() {
+    let (client, user, database_connection) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let other_user = User::register(
+        &database_connection,
+        "other".to_string(),
+        "other@domain.com".to_string(),
+        TEST_PASSWORD.to_string(),
+    )
+    .expect("user should be registered");
+    let (personal_access_token, token) = PersonalAccessToken::new(
+        &database_connection,
+        &other_user,
+        "Import script".to_string(),
+        vec![Scope::SentencesRead],
+        None,
+    )
+    .expect("personal access token should be created");
+
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+    let url = format!("/auth/tokens/{}", personal_access_token.id);
+    let response = send_delete_request_with_auth(&client, &url, &access_token);
+    assert_eq!(response.status(), Status::NotFound);
+
+    let response = send_get_request_with_auth(&client, "/sentences", &token);
+    assert_eq!(response.status(), Status::Ok);
+}
+
+#[test]
+fn logout_all_should_revoke_tokens() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+    let personal_access_token = create_personal_access_token(
+        &client,
+        &access_token,
+        json!({ "name": "Import script", "scopes": ["sentences:read"] }),
+    );
+    let token = personal_access_token["token"].as_str().unwrap().to_string();
+
+    let response =
+        send_post_request_with_json_and_auth(&client, "/auth/logout-all", &access_token, json!({}));
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = send_get_request_with_auth(&client, "/sentences", &token);
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_fail(&response_to_json(response), "Revoked Token Provided");
+}
+
+#[test]
+fn change_password_should_revoke_tokens() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+    let personal_access_token = create_personal_access_token(
+        &client,
+        &access_token,
+        json!({ "name": "Import script", "scopes": ["sentences:read"] }),
+    );
+    let token = personal_access_token["token"].as_str().unwrap().to_string();
+
+    let response = send_put_request_with_json_and_auth(
+        &client,
+        "/auth/password",
+        &access_token,
+        json!({
+            "current_password": TEST_PASSWORD,
+            "new_password": "new password",
+        }),
+    );
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = send_get_request_with_auth(&client, "/sentences", &token);
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_fail(&response_to_json(response), "Revoked Token Provided");
+}
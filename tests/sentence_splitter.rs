@@ -0,0 +1,43 @@
+use sentence_base::analyzer::sentence_splitter::split_sentences;
+
+#[test]
+fn split_sentences_should_split_on_terminators() {
+    assert_eq!(
+        split_sentences("猫が好きです。犬は？本当に！やった!!"),
+        vec!["猫が好きです。", "犬は？", "本当に！", "やった!!"]
+    );
+}
+
+#[test]
+fn split_sentences_should_keep_quotes_together() {
+    assert_eq!(
+        split_sentences("「行こう！早く。」と彼は言った。『本』を読む。"),
+        vec!["「行こう！早く。」と彼は言った。", "『本』を読む。"]
+    );
+}
+
+#[test]
+fn split_sentences_should_attach_closing_quotes_to_terminated_sentences() {
+    assert_eq!(
+        split_sentences("行こう！」そうだね。"),
+        vec!["行こう！」", "そうだね。"]
+    );
+}
+
+#[test]
+fn split_sentences_should_split_on_newlines_and_trim() {
+    assert_eq!(
+        split_sentences("　第一章\r\n\n  雨が降った\n「まだ\n終わらない」"),
+        vec!["第一章", "雨が降った", "「まだ", "終わらない」"]
+    );
+}
+
+#[test]
+fn split_sentences_should_not_let_an_unbalanced_quote_swallow_the_paragraph() {
+    let unbalanced_quote = format!("「{}。", "あ".repeat(100));
+
+    assert_eq!(
+        split_sentences(&format!("{}次の文。最後の文。", unbalanced_quote)),
+        vec![unbalanced_quote.as_str(), "次の文。", "最後の文。"]
+    );
+}
@@ -4,10 +4,11 @@ use diesel::RunQueryDsl;
 use diesel::{BelongingToDsl, ExpressionMethods};
 use diesel::{PgConnection, QueryDsl};
 use itertools::__std_iter::FromIterator;
-use rocket::http::Status;
+use rocket::http::{ContentType, Status};
 use rocket::local::blocking::Client;
 use rocket::serde::json::Value;
 use rocket::serde::{Deserialize, Serialize};
+use sentence_base::frequency_list::jlpt::JlptLevel;
 use sentence_base::helpers::get_maximum_pending_sentences;
 use sentence_base::jwt::TokenType;
 use sentence_base::models::sentence::Sentence;
@@ -145,6 +146,8 @@ fn new_should_result_with_a_word_and_a_sentence_added() {
     );
     assert_eq!(deserialized_data.sentence.reading, word.reading);
     assert_eq!(deserialized_data.sentence.mining_frequency, word.frequency);
+    assert_eq!(deserialized_data.sentence.jlpt_level, Some(JlptLevel::N5));
+    assert_eq!(deserialized_data.sentence.difficulty, None);
 }
 
 #[test]
@@ -346,6 +349,170 @@ fn get_should_return_pending_sentences_in_the_correct_order() {
             ("ペン", "ペン"),
         ],
     );
+    // Only scored when sorting by difficulty.
+    assert!(data["sentences"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|sentence| sentence["difficulty"].is_null()));
+}
+
+#[test]
+fn get_should_filter_pending_sentences_by_jlpt_level() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    mine_test_words(&client, &access_token);
+
+    let response = send_get_request_with_auth(&client, "/sentences?jlpt_level=N5", &access_token);
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+
+    let data = json.get("data").unwrap().as_object().unwrap();
+
+    assert_word_order(
+        &data,
+        vec![
+            ("家", "イエ"),
+            ("学校", "ガッコウ"),
+            ("犬", "イヌ"),
+            ("猫", "ネコ"),
+            ("ペン", "ペン"),
+        ],
+    );
+
+    let response = send_get_request_with_auth(&client, "/sentences?jlpt_level=1", &access_token);
+    let json = response_to_json(response);
+    let data = json.get("data").unwrap().as_object().unwrap();
+    assert_word_order(&data, vec![]);
+}
+
+#[test]
+fn get_should_reject_invalid_jlpt_level() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_get_request_with_auth(&client, "/sentences?jlpt_level=N6", &access_token);
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    let json = response_to_json(response);
+    assert_fail(&json, "Invalid JLPT Level");
+}
+
+#[test]
+fn get_should_order_pending_sentences_by_difficulty() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    for (dictionary_form, reading, sentence) in [
+        (
+            "魑魅魍魎",
+            "チミモウリョウ",
+            "魑魅魍魎が跋扈する世の中を憂える。",
+        ),
+        ("猫", "ネコ", "猫です。"),
+        ("ペン", "ペン", "これはペンです。"),
+    ] {
+        let response = send_post_request_with_json_and_auth(
+            &client,
+            "/sentences",
+            &access_token,
+            json!({
+                "dictionary_form": dictionary_form,
+                "reading": reading,
+                "sentence": sentence,
+            }),
+        );
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    let response =
+        send_get_request_with_auth(&client, "/sentences?order=difficulty", &access_token);
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+
+    let sentences = json["data"]["sentences"].as_array().unwrap();
+    let difficulties = sentences
+        .iter()
+        .map(|sentence| {
+            sentence["difficulty"]
+                .as_f64()
+                .expect("'difficulty' should be a number")
+        })
+        .collect::<Vec<f64>>();
+
+    assert_eq!(difficulties.len(), 3);
+    assert!(difficulties.windows(2).all(|pair| pair[0] <= pair[1]));
+    assert_eq!(sentences[2]["dictionary_form"], "魑魅魍魎");
+}
+
+#[test]
+fn get_should_reject_invalid_order() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_get_request_with_auth(&client, "/sentences?order=random", &access_token);
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    let json = response_to_json(response);
+    assert_fail(&json, "Invalid Order");
+}
+
+#[test]
+fn get_should_order_pending_sentences_by_custom_frequency_list() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+
+    let response = send_put_request_with_body_and_auth(
+        &client,
+        "/frequency-lists/custom",
+        &access_token,
+        ContentType::CSV,
+        "ペン,ペン,100\n猫,ネコ,50\n犬,,40\n学校,ガッコウ,30\n",
+    );
+    assert_eq!(response.status(), Status::Ok);
+
+    mine_test_words(&client, &access_token);
+
+    let response = send_get_request_with_auth(&client, "/sentences", &access_token);
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+
+    let data = json.get("data").unwrap().as_object().unwrap();
+
+    assert_word_order(
+        &data,
+        vec![
+            ("魑魅魍魎", "チミモウリョウ"),
+            ("魑魅魍魎", "チミモウリョウ"),
+            ("魑魅魍魎", "チミモウリョウ"),
+            ("勝ち星", "カチボシ"),
+            ("勝ち星", "カチボシ"),
+            ("ペン", "ペン"),
+            ("猫", "ネコ"),
+            ("犬", "イヌ"),
+            ("学校", "ガッコウ"),
+            ("家", "イエ"),
+        ],
+    );
+
+    let sentences = data.get("sentences").unwrap().as_array().unwrap();
+    assert_eq!(sentences[7].get("dictionary_frequency").unwrap(), 2);
+    assert_eq!(
+        sentences[7].get("dictionary_frequency_match").unwrap(),
+        "dictionary_form"
+    );
+    assert_eq!(sentences[9].get("dictionary_frequency").unwrap(), 5);
+    assert_eq!(
+        sentences[9].get("dictionary_frequency_match").unwrap(),
+        "not_found"
+    );
 }
 
 fn assert_word_order(data: &Map<String, Value>, order: Vec<(&str, &str)>) {
@@ -0,0 +1,268 @@
+use common::*;
+use jwt::VerifyWithKey;
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::blocking::{Client, LocalResponse};
+use sentence_base::jwt::{get_jwt_secret_hmac, TokenClaims, TokenType};
+use sentence_base::models::user::User;
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+
+mod common;
+
+const TEST_USER_AGENT: &'static str = "Mozilla/5.0 (X11; Linux x86_64)";
+
+fn login_from_device(client: &Client, device_name: &str, remote: &str) -> (String, String) {
+    let response = client
+        .post("/auth/login")
+        .header(ContentType::JSON)
+        .header(Header::new("User-Agent", TEST_USER_AGENT))
+        .remote(remote.parse::<SocketAddr>().unwrap())
+        .body(
+            json!({
+                "email": TEST_EMAIL,
+                "password": TEST_PASSWORD,
+                "device_name": device_name,
+            })
+            .to_string(),
+        )
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+
+    (
+        json["data"]["access_token"].as_str().unwrap().to_string(),
+        json["data"]["refresh_token"].as_str().unwrap().to_string(),
+    )
+}
+
+fn send_refresh_request<'a>(client: &'a Client, token: &'a String) -> LocalResponse<'a> {
+    send_post_request_with_json(
+        &client,
+        "/auth/refresh",
+        json!({
+            "refresh_token": token,
+        }),
+    )
+}
+
+fn get_sessions(client: &Client, access_token: &String) -> Vec<Value> {
+    let response = send_get_request_with_auth(client, "/auth/sessions", access_token);
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    assert_success(&json);
+
+    json["data"]["sessions"].as_array().unwrap().clone()
+}
+
+fn get_claims(refresh_token: &str) -> TokenClaims {
+    refresh_token
+        .verify_with_key(&get_jwt_secret_hmac())
+        .expect("key should be verified")
+}
+
+fn get_session_id(refresh_token: &str) -> Option<i32> {
+    get_claims(refresh_token).sid
+}
+
+#[test]
+fn get_all_should_require_auth() {
+    let (client, _) = create_client();
+
+    let response = send_get_request(&client, "/auth/sessions");
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[test]
+fn login_should_open_a_session() {
+    let (client, _, _) = create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+
+    let (access_token, refresh_token) = login_from_device(&client, "Phone", "10.0.0.1:4000");
+    let sessions = get_sessions(&client, &access_token);
+
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0]["device_name"], "Phone");
+    assert_eq!(sessions[0]["user_agent"], TEST_USER_AGENT);
+    assert_eq!(sessions[0]["last_ip"], "10.0.0.1");
+    assert_eq!(sessions[0].get("user_id"), None);
+    assert_eq!(
+        get_session_id(&refresh_token),
+        sessions[0]["id"].as_i64().map(|id| id as i32)
+    );
+    assert_eq!(
+        get_session_id(&access_token),
+        get_session_id(&refresh_token)
+    );
+    assert_eq!(get_claims(&access_token).jti, None);
+}
+
+#[test]
+fn refresh_should_keep_the_session() {
+    let (client, _, _) = create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let (access_token, refresh_token) = login_from_device(&client, "Phone", "10.0.0.1:4000");
+
+    let response = send_refresh_request(&client, &refresh_token);
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    let new_refresh_token = json["data"]["refresh_token"].as_str().unwrap();
+
+    assert_eq!(
+        get_session_id(new_refresh_token),
+        get_session_id(&refresh_token)
+    );
+    assert_eq!(get_sessions(&client, &access_token).len(), 1);
+}
+
+#[test]
+fn refresh_should_rotate_the_refresh_token() {
+    let (client, _, _) = create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let (_, refresh_token) = login_from_device(&client, "Phone", "10.0.0.1:4000");
+
+    let response = send_refresh_request(&client, &refresh_token);
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    let new_refresh_token = json["data"]["refresh_token"].as_str().unwrap().to_string();
+
+    let token_id = get_claims(&refresh_token).jti;
+    let new_token_id = get_claims(&new_refresh_token).jti;
+    assert!(token_id.is_some());
+    assert!(new_token_id.is_some());
+    assert_ne!(token_id, new_token_id);
+
+    let response = send_refresh_request(&client, &new_refresh_token);
+    assert_eq!(response.status(), Status::Ok);
+}
+
+#[test]
+fn refresh_should_revoke_the_session_on_reuse() {
+    let (client, _, _) = create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let (access_token, refresh_token) = login_from_device(&client, "Phone", "10.0.0.1:4000");
+    let (laptop_access_token, _) = login_from_device(&client, "Laptop", "10.0.0.2:4000");
+
+    let response = send_refresh_request(&client, &refresh_token);
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    let new_refresh_token = json["data"]["refresh_token"].as_str().unwrap().to_string();
+
+    let response = send_refresh_request(&client, &refresh_token);
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_fail(&response_to_json(response), "Reused Token Provided");
+
+    let response = send_refresh_request(&client, &new_refresh_token);
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_fail(
+        &response_to_json(response),
+        "Token of Revoked Session Provided",
+    );
+
+    let response = send_get_request_with_auth(&client, "/auth/sessions", &access_token);
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_fail(
+        &response_to_json(response),
+        "Token of Revoked Session Provided",
+    );
+
+    let sessions = get_sessions(&client, &laptop_access_token);
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0]["device_name"], "Laptop");
+    assert_eq!(sessions[0].get("refresh_token_id"), None);
+}
+
+#[test]
+fn refresh_should_reject_tokens_without_a_session() {
+    let (client, user, _) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let access_token = generate_jwt_token_for_user(&user, TokenType::Access);
+    let refresh_token = generate_jwt_token_for_user(&user, TokenType::Refresh);
+
+    for _ in 0..2 {
+        let response = send_refresh_request(&client, &refresh_token);
+        assert_eq!(response.status(), Status::Unauthorized);
+        assert_fail(
+            &response_to_json(response),
+            "Token of Revoked Session Provided",
+        );
+    }
+
+    assert!(get_sessions(&client, &access_token).is_empty());
+}
+
+#[test]
+fn delete_should_revoke_only_the_session() {
+    let (client, _, _) = create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let (phone_access_token, phone_refresh_token) =
+        login_from_device(&client, "Phone", "10.0.0.1:4000");
+    let (laptop_access_token, laptop_refresh_token) =
+        login_from_device(&client, "Laptop", "10.0.0.2:4000");
+
+    let phone_session_id = get_session_id(&phone_refresh_token).unwrap();
+    let url = format!("/auth/sessions/{}", phone_session_id);
+    let response = send_delete_request_with_auth(&client, &url, &laptop_access_token);
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = send_refresh_request(&client, &phone_refresh_token);
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_fail(
+        &response_to_json(response),
+        "Token of Revoked Session Provided",
+    );
+    let response = send_get_request_with_auth(&client, "/auth/me", &phone_access_token);
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_fail(
+        &response_to_json(response),
+        "Token of Revoked Session Provided",
+    );
+
+    let response = send_refresh_request(&client, &laptop_refresh_token);
+    assert_eq!(response.status(), Status::Ok);
+
+    let sessions = get_sessions(&client, &laptop_access_token);
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0]["device_name"], "Laptop");
+
+    let response = send_delete_request_with_auth(&client, &url, &laptop_access_token);
+    assert_eq!(response.status(), Status::NotFound);
+    assert_fail(&response_to_json(response), "Session Not Found");
+}
+
+#[test]
+fn delete_should_not_revoke_non_owned_sessions() {
+    let (client, _, database_connection) =
+        create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    let (_, refresh_token) = login_from_device(&client, "Phone", "10.0.0.1:4000");
+    let other_user = User::register(
+        &database_connection,
+        "other".to_string(),
+        "other@domain.com".to_string(),
+        TEST_PASSWORD.to_string(),
+    )
+    .expect("user should be registered");
+    let other_access_token = generate_jwt_token_for_user(&other_user, TokenType::Access);
+
+    let url = format!("/auth/sessions/{}", get_session_id(&refresh_token).unwrap());
+    let response = send_delete_request_with_auth(&client, &url, &other_access_token);
+    assert_eq!(response.status(), Status::NotFound);
+
+    let response = send_refresh_request(&client, &refresh_token);
+    assert_eq!(response.status(), Status::Ok);
+}
+
+#[test]
+fn logout_all_should_close_every_other_session() {
+    let (client, _, _) = create_client_and_register_user(TEST_USERNAME, TEST_EMAIL, TEST_PASSWORD);
+    login_from_device(&client, "Phone", "10.0.0.1:4000");
+    let (access_token, _) = login_from_device(&client, "Laptop", "10.0.0.2:4000");
+
+    let response =
+        send_post_request_with_json_and_auth(&client, "/auth/logout-all", &access_token, json!({}));
+    assert_eq!(response.status(), Status::Ok);
+    let json = response_to_json(response);
+    let new_access_token = json["data"]["access_token"].as_str().unwrap().to_string();
+    let new_refresh_token = json["data"]["refresh_token"].as_str().unwrap();
+
+    let sessions = get_sessions(&client, &new_access_token);
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(
+        get_session_id(new_refresh_token),
+        sessions[0]["id"].as_i64().map(|id| id as i32)
+    );
+}